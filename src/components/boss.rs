@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use crate::resources::game_config::EnemyType;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BossState {
+    Chase,
+    ChargeWindup,
+    Charging,
+    Shockwave,
+    Summon,
+}
+
+#[derive(Component)]
+pub struct Boss {
+    pub phase: u8,
+    pub state: BossState,
+    pub state_timer: Timer,
+    pub charge_direction: Vec2,
+    // Đếm số đòn đã ra để xoay vòng các kiểu tấn công
+    pub attack_count: u32,
+    pub base_color: Color,
+    pub summon: EnemyType,
+}
+
+impl Boss {
+    pub fn new(base_color: Color, summon: EnemyType) -> Self {
+        Self {
+            phase: 1,
+            state: BossState::Chase,
+            state_timer: Timer::from_seconds(2.0, TimerMode::Once),
+            charge_direction: Vec2::ZERO,
+            attack_count: 0,
+            base_color,
+            summon,
+        }
+    }
+}
+
+// Điểm yếu ở lưng boss, luôn quay ngược phía player. Chỉ đạn đã nảy ít nhất
+// một lần và trúng vào cung này mới gây sát thương, trúng thân thì bị dội lại.
+#[derive(Component)]
+pub struct WeakPoint {
+    pub facing: Vec2,
+}
+
+impl WeakPoint {
+    // Cung ±45° quanh hướng điểm yếu
+    const ARC_COS: f32 = 0.7;
+
+    pub fn is_hit(&self, to_bullet: Vec2) -> bool {
+        to_bullet.dot(self.facing) > Self::ARC_COS
+    }
+}
+
+#[derive(Component)]
+pub struct WeakPointVisual;
+
+#[derive(Component)]
+pub struct Shockwave {
+    pub radius: f32,
+    pub max_radius: f32,
+    pub speed: f32,
+    pub damage: f32,
+    pub has_hit: bool,
+}
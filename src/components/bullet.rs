@@ -1,4 +1,12 @@
 use bevy::prelude::*;
+use crate::components::status::StatusEffect;
+
+// Phe của đạn quyết định nó gây sát thương cho ai
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Faction {
+    Player,
+    Enemy,
+}
 
 #[derive(Component)]
 pub struct Bullet {
@@ -9,6 +17,17 @@ pub struct Bullet {
     pub is_despawning: bool,
     pub hit_entities: Vec<Entity>,
     pub damage: f32,
+    // Entity đã bắn ra viên đạn, giữ nguyên kể cả khi bị aura đá ngược lại
+    pub owner: Option<Entity>,
+    pub faction: Faction,
+    // Số lần nảy kể từ lần bắn / đá gần nhất
+    pub bounces: u32,
+    // Hiệu ứng gắn lên quái khi trúng (Burn, Freeze, ...)
+    pub effects: Vec<StatusEffect>,
+    // Đạn đang nằm trong aura ở frame trước, để biết lúc nó vừa đi vào
+    pub in_aura: bool,
+    // Hệ số sát thương của cú parry gần nhất, mất ở lần đá kế tiếp
+    pub parry_bonus: f32,
 }
 
 impl Default for Bullet {
@@ -21,6 +40,26 @@ impl Default for Bullet {
             is_despawning: false,
             hit_entities: Vec::new(),
             damage: 1.0,
+            owner: None,
+            faction: Faction::Player,
+            bounces: 0,
+            effects: Vec::new(),
+            in_aura: false,
+            parry_bonus: 1.0,
         }
     }
-}
\ No newline at end of file
+}
+
+impl Bullet {
+    pub fn hit_damage(&self) -> f32 {
+        self.damage * self.parry_bonus
+    }
+}
+
+// Đạn đang bị gọi về phía player theo đường cong
+#[derive(Component)]
+pub struct Recalling {
+    // Cong sang trái (-1) hay phải (1) so với đường thẳng về player
+    pub side: f32,
+    pub start_distance: f32,
+}
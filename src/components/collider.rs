@@ -11,6 +11,10 @@ impl Collider {
             half_size: Vec2::new(width / 2.0, height / 2.0),
         }
     }
+
+    pub fn radius(&self) -> f32 {
+        self.half_size.max_element()
+    }
 }
 
 #[derive(Component)]
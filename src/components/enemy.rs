@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use std::sync::Arc;
+use crate::resources::game_config::EnemyType;
+use crate::resources::loot::LootTable;
 
 #[derive(Component)]
 pub struct Enemy;
@@ -18,6 +21,50 @@ impl Health {
     pub fn new(amount: f32) -> Self {
         Self { current: amount, max: amount }
     }
+
+    // Hồi máu không vượt quá máu tối đa
+    pub fn heal(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
+    pub fn set_max(&mut self, max: f32) {
+        self.max = max;
+        self.current = self.current.min(max);
+    }
+}
+
+// Hướng bọc sườn: -1.0 vòng bên trái, 1.0 vòng bên phải player
+#[derive(Component)]
+pub struct CrowdAgent {
+    pub flank_side: f32,
+}
+
+#[derive(Component)]
+pub struct RangedAttack {
+    pub cooldown: Timer,
+    pub preferred_distance: f32,
+}
+
+// Khiên chặn đạn bắn trúng mặt trước, hướng theo chiều di chuyển của quái
+#[derive(Component)]
+pub struct Shield {
+    pub facing: Vec2,
+}
+
+#[derive(Component)]
+pub struct ShieldVisual;
+
+// Khi chết tách ra thành các quái con nhỏ và nhanh hơn
+#[derive(Component)]
+pub struct Splitter {
+    pub child: EnemyType,
+    pub children: u32,
+    pub difficulty_multiplier: f32,
+}
+
+#[derive(Component)]
+pub struct EnemyLoot {
+    pub table: Arc<LootTable>,
 }
 
 #[derive(Component)]
@@ -28,12 +75,28 @@ pub struct Damage {
 #[derive(Component)]
 pub struct EnemyAnimationTimer {
     pub timer: Timer,
+    pub frames: usize,
 }
 
-impl Default for EnemyAnimationTimer {
-    fn default() -> Self {
+impl EnemyAnimationTimer {
+    pub fn new(fps: f32, frames: usize) -> Self {
         Self {
-            timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+            timer: Timer::from_seconds(1.0 / fps, TimerMode::Repeating),
+            frames,
         }
     }
-}
\ No newline at end of file
+}
+// Chạm player thì gây sát thương rồi chờ hồi chiêu, không tự huỷ
+#[derive(Component)]
+pub struct ContactAttack {
+    pub cooldown: Timer,
+}
+
+impl ContactAttack {
+    pub fn new(seconds: f32) -> Self {
+        let mut cooldown = Timer::from_seconds(seconds, TimerMode::Once);
+        // Sẵn sàng đánh ngay lần chạm đầu tiên
+        cooldown.set_elapsed(std::time::Duration::from_secs_f32(seconds));
+        Self { cooldown }
+    }
+}
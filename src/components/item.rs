@@ -1,6 +1,31 @@
 use bevy::prelude::*;
+use crate::resources::relics::RelicKind;
 
 #[derive(Component)]
 pub struct Coin {
     pub value: u32,
+    // Vận tốc văng ra lúc rơi, giảm dần theo ma sát
+    pub velocity: Vec2,
+    // Hết giờ thì coin biến mất, nhấp nháy ở mấy giây cuối
+    pub lifetime: Timer,
+}
+
+impl Coin {
+    pub fn new(value: u32, velocity: Vec2, lifetime: f32) -> Self {
+        Self {
+            value,
+            velocity,
+            lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
+        }
+    }
+}
+
+// Vỏ đạn rơi xuống đất khi player hết tiền bắn, nhặt lại được một phát miễn phí
+#[derive(Component)]
+pub struct DroppedBullet;
+
+// Relic rơi ra khi hạ boss, chạm vào để nhặt
+#[derive(Component)]
+pub struct RelicPickup {
+    pub relic: RelicKind,
 }
@@ -4,4 +4,7 @@ pub mod bullet;
 pub mod collider;
 pub mod enemy;
 pub mod particle;
-pub mod item;
\ No newline at end of file
+pub mod item;
+pub mod boss;
+pub mod status;
+pub mod power_up;
\ No newline at end of file
@@ -1,23 +1,75 @@
 use bevy::prelude::*;
+use crate::components::stats::{StatKind, Stats};
 
 #[derive(Component)]
 pub struct Player;
 
 #[derive(Component)]
-pub struct MovementStats {
-    pub speed: f32,
+pub struct Aura;
+
+// Năng lượng aura: giữ Shift làm chậm đạn thì hao dần, Space đá đạn thì tốn một khoản.
+// Sức chứa, tốc độ hồi và bán kính là chỉ số trong `Stats`.
+#[derive(Component)]
+pub struct AuraEnergy {
+    pub current: f32,
+}
+
+impl AuraEnergy {
+    pub fn full(stats: &Stats) -> Self {
+        Self { current: stats.get(StatKind::AuraCapacity) }
+    }
+
+    pub fn can_spend(&self, amount: f32) -> bool {
+        self.current >= amount
+    }
+}
+
+#[derive(Component)]
+pub struct HasAura;
+
+// Khoảng bất tử sau khi trúng đòn, sprite nhấp nháy trong lúc này
+#[derive(Component)]
+pub struct Invulnerable {
+    pub timer: Timer,
+    pub blink_timer: Option<Timer>,
 }
 
-impl Default for MovementStats {
-    fn default() -> Self {
+impl Invulnerable {
+    pub fn new(seconds: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+            blink_timer: Some(Timer::from_seconds(0.1, TimerMode::Repeating)),
+        }
+    }
+
+    // Bất tử ngắn không nhấp nháy, dùng khi dash
+    pub fn without_blink(seconds: f32) -> Self {
         Self {
-            speed: 300.0, 
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+            blink_timer: None,
         }
     }
 }
 
+// Số charge tối đa và quãng dash là chỉ số trong `Stats`
 #[derive(Component)]
-pub struct Aura;
+pub struct Dash {
+    pub charges: u32,
+    // Thời gian hồi một charge
+    pub recharge: Timer,
+    pub direction: Vec2,
+    pub timer: Timer,
+    pub is_dashing: bool,
+}
 
-#[derive(Component)]
-pub struct HasAura;
\ No newline at end of file
+impl Dash {
+    pub fn full(stats: &Stats) -> Self {
+        Self {
+            charges: stats.count(StatKind::DashCharges),
+            recharge: Timer::from_seconds(1.2, TimerMode::Once),
+            direction: Vec2::X,
+            timer: Timer::from_seconds(0.15, TimerMode::Once),
+            is_dashing: false,
+        }
+    }
+}
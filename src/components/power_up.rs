@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PowerUpKind {
+    RapidFire,
+    DoubleDamage,
+    ShieldBubble,
+    Magnet,
+    SlowField,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 5] = [
+        PowerUpKind::RapidFire,
+        PowerUpKind::DoubleDamage,
+        PowerUpKind::ShieldBubble,
+        PowerUpKind::Magnet,
+        PowerUpKind::SlowField,
+    ];
+
+    // Dùng làm nguồn của modifier trong Stats
+    pub fn id(self) -> &'static str {
+        match self {
+            PowerUpKind::RapidFire => "rapid_fire",
+            PowerUpKind::DoubleDamage => "double_damage",
+            PowerUpKind::ShieldBubble => "shield_bubble",
+            PowerUpKind::Magnet => "magnet",
+            PowerUpKind::SlowField => "slow_field",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PowerUpKind::RapidFire => "RAPID",
+            PowerUpKind::DoubleDamage => "x2 DMG",
+            PowerUpKind::ShieldBubble => "SHIELD",
+            PowerUpKind::Magnet => "MAGNET",
+            PowerUpKind::SlowField => "SLOW",
+        }
+    }
+
+    pub fn duration(self) -> f32 {
+        match self {
+            PowerUpKind::RapidFire => 8.0,
+            PowerUpKind::DoubleDamage => 8.0,
+            PowerUpKind::ShieldBubble => 5.0,
+            PowerUpKind::Magnet => 10.0,
+            PowerUpKind::SlowField => 6.0,
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            PowerUpKind::RapidFire => Color::srgb(1.0, 0.9, 0.3),
+            PowerUpKind::DoubleDamage => Color::srgb(1.0, 0.3, 0.3),
+            PowerUpKind::ShieldBubble => Color::srgb(0.4, 0.8, 1.0),
+            PowerUpKind::Magnet => Color::srgb(0.9, 0.5, 1.0),
+            PowerUpKind::SlowField => Color::srgb(0.5, 1.0, 0.7),
+        }
+    }
+}
+
+// Power-up nằm trên sàn, tự biến mất nếu không nhặt kịp
+#[derive(Component)]
+pub struct PowerUpPickup {
+    pub kind: PowerUpKind,
+    pub lifetime: Timer,
+}
+
+pub struct ActivePowerUp {
+    pub kind: PowerUpKind,
+    pub timer: Timer,
+}
+
+/// Các power-up đang có hiệu lực trên player, HUD đọc từ đây để hiện đếm ngược.
+#[derive(Component, Default)]
+pub struct ActivePowerUps {
+    pub active: Vec<ActivePowerUp>,
+}
+
+impl ActivePowerUps {
+    pub fn get(&self, kind: PowerUpKind) -> Option<&ActivePowerUp> {
+        self.active.iter().find(|p| p.kind == kind)
+    }
+
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    // Nhặt lại cùng loại thì làm mới thời gian, không cộng dồn
+    pub fn activate(&mut self, kind: PowerUpKind) {
+        let timer = Timer::from_seconds(kind.duration(), TimerMode::Once);
+        match self.active.iter_mut().find(|p| p.kind == kind) {
+            Some(existing) => existing.timer = timer,
+            None => self.active.push(ActivePowerUp { kind, timer }),
+        }
+    }
+}
+
+// Sprite con của player hiển thị power-up đang bật (bong bóng khiên, vùng làm chậm)
+#[derive(Component)]
+pub struct PowerUpVisual {
+    pub kind: PowerUpKind,
+}
@@ -1,27 +1,235 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::status::StatusEffect;
 
+/// Tài nguyên của player trong một run: tiền, đạn, hiệu ứng đạn.
+/// Các chỉ số nâng cấp được (máu, tốc độ, sát thương, ...) nằm trong `Stats`.
 #[derive(Component)]
 pub struct PlayerStats {
-    pub current_hp: f32,
-    pub max_hp: f32,
     pub money: u32,
-    pub damage: f32,
+    // Hiệu ứng mua ở shop, gắn vào mọi viên đạn bắn ra
+    pub bullet_effects: Vec<StatusEffect>,
+    // Số phát bắn miễn phí nhặt được từ vỏ đạn rơi
+    pub spare_bullets: u32,
+}
+
+impl PlayerStats {
+    pub fn add_bullet_effect(&mut self, effect: StatusEffect) {
+        match self.bullet_effects.iter_mut().find(|e| e.kind == effect.kind) {
+            Some(existing) => existing.merge(&effect),
+            None => self.bullet_effects.push(effect),
+        }
+    }
 }
 
 impl Default for PlayerStats {
     fn default() -> Self {
         Self {
-            current_hp: 5.0,
-            max_hp:5.0, 
             money: 10,
-            damage: 1.0,
+            bullet_effects: Vec::new(),
+            spare_bullets: 0,
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatKind {
+    MaxHp,
+    MoveSpeed,
+    Damage,
+    BulletSpeed,
+    BulletLifetime,
+    // Tốc độ đạn sau khi bị aura đá
+    KickSpeed,
+    // Coin trong bán kính này tự bay về phía player
+    MagnetRange,
+    // Số viên đạn của player được tồn tại cùng lúc
+    MaxBullets,
+    AuraCapacity,
+    // Năng lượng aura hồi mỗi giây khi không giữ Shift
+    AuraRegen,
+    AuraRadius,
+    DashCharges,
+    DashDistance,
+    // Tiền trả cho mỗi phát bắn
+    BulletCost,
+}
+
+impl StatKind {
+    pub const ALL: [StatKind; 14] = [
+        StatKind::MaxHp,
+        StatKind::MoveSpeed,
+        StatKind::Damage,
+        StatKind::BulletSpeed,
+        StatKind::BulletLifetime,
+        StatKind::KickSpeed,
+        StatKind::MagnetRange,
+        StatKind::MaxBullets,
+        StatKind::AuraCapacity,
+        StatKind::AuraRegen,
+        StatKind::AuraRadius,
+        StatKind::DashCharges,
+        StatKind::DashDistance,
+        StatKind::BulletCost,
+    ];
+    // Kích thước mảng chỉ số lấy theo ALL, thêm biến thể thì thêm cả vào ALL
+    const COUNT: usize = Self::ALL.len();
+}
+
+/// Một lớp modifier: cộng `add` vào chỉ số gốc rồi nhân với `multiply`.
+pub struct StatModifier {
+    pub stat: StatKind,
+    pub add: f32,
+    pub multiply: f32,
+    // None = vĩnh viễn (mua ở shop), Some = buff có thời hạn
+    pub timer: Option<Timer>,
+    // Nguồn của modifier (vd. id power-up) để gỡ hoặc làm mới cả nhóm
+    pub source: Option<&'static str>,
+}
+
+impl StatModifier {
+    pub fn add(stat: StatKind, amount: f32) -> Self {
+        Self { stat, add: amount, multiply: 1.0, timer: None, source: None }
+    }
+
+    pub fn multiply(stat: StatKind, factor: f32) -> Self {
+        Self { stat, add: 0.0, multiply: factor, timer: None, source: None }
+    }
+
+    pub fn timed(mut self, seconds: f32, source: &'static str) -> Self {
+        self.timer = Some(Timer::from_seconds(seconds, TimerMode::Once));
+        self.source = Some(source);
+        self
+    }
+}
+
+/// Chỉ số gốc cộng các chồng modifier. Giá trị cuối = (gốc + tổng cộng) * tích nhân,
+/// chỉ tính lại khi danh sách modifier thay đổi.
+#[derive(Component)]
+pub struct Stats {
+    base: [f32; StatKind::COUNT],
+    modifiers: Vec<StatModifier>,
+    values: [f32; StatKind::COUNT],
+}
+
+impl Stats {
+    pub fn player() -> Self {
+        let mut base = [0.0; StatKind::COUNT];
+        base[StatKind::MaxHp as usize] = 5.0;
+        base[StatKind::MoveSpeed as usize] = 300.0;
+        base[StatKind::Damage as usize] = 1.0;
+        base[StatKind::BulletSpeed as usize] = 350.0;
+        base[StatKind::BulletLifetime as usize] = 12.0;
+        base[StatKind::KickSpeed as usize] = 400.0;
+        base[StatKind::MagnetRange as usize] = 40.0;
+        base[StatKind::MaxBullets as usize] = 1.0;
+        base[StatKind::AuraCapacity as usize] = 100.0;
+        base[StatKind::AuraRegen as usize] = 15.0;
+        base[StatKind::AuraRadius as usize] = 60.0;
+        base[StatKind::DashCharges as usize] = 1.0;
+        base[StatKind::DashDistance as usize] = 160.0;
+        base[StatKind::BulletCost as usize] = 3.0;
+
+        Self {
+            base,
+            modifiers: Vec::new(),
+            values: base,
+        }
+    }
+
+    pub fn get(&self, stat: StatKind) -> f32 {
+        self.values[stat as usize]
+    }
+
+    // Chỉ số đếm được (charge, số đạn, ...) làm tròn về số nguyên không âm
+    pub fn count(&self, stat: StatKind) -> u32 {
+        self.get(stat).round().max(0.0) as u32
+    }
+
+    // Giảm giá bao nhiêu thì mỗi phát bắn vẫn tốn ít nhất 1 tiền
+    pub fn bullet_cost(&self) -> u32 {
+        self.count(StatKind::BulletCost).max(1)
+    }
+
+    pub fn add_modifier(&mut self, modifier: StatModifier) {
+        self.modifiers.push(modifier);
+        self.recompute();
+    }
+
+    // Modifier mua ở shop, không tính buff có thời hạn
+    pub fn permanent_modifiers(&self) -> impl Iterator<Item = &StatModifier> {
+        self.modifiers.iter().filter(|m| m.timer.is_none())
+    }
+
+    pub fn remove_source(&mut self, source: &str) {
+        self.modifiers.retain(|m| m.source != Some(source));
+        self.recompute();
+    }
+
+    // Đếm ngược modifier có thời hạn, bỏ những cái đã hết và tính lại nếu cần
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        let before = self.modifiers.len();
+        for timer in self.modifiers.iter_mut().filter_map(|m| m.timer.as_mut()) {
+            timer.tick(delta);
+        }
+        self.modifiers.retain(|m| !m.timer.as_ref().is_some_and(Timer::finished));
+
+        if self.modifiers.len() != before {
+            self.recompute();
+        }
+    }
+
+    fn recompute(&mut self) {
+        let mut added = self.base;
+        let mut multiplier = [1.0; StatKind::COUNT];
+        for modifier in &self.modifiers {
+            added[modifier.stat as usize] += modifier.add;
+            multiplier[modifier.stat as usize] *= modifier.multiply;
+        }
+        for i in 0..StatKind::COUNT {
+            self.values[i] = added[i] * multiplier[i];
+        }
+    }
+}
 
 #[derive(Component)]
 pub struct HpText;
 
 #[derive(Component)]
-pub struct MoneyText;
\ No newline at end of file
+pub struct MoneyText;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Không có nhánh `_`: thêm biến thể mới thì file này không biên dịch cho tới khi
+    // biến thể đó được đưa vào đây và vào StatKind::ALL
+    fn is_listed(stat: StatKind) -> bool {
+        match stat {
+            StatKind::MaxHp
+            | StatKind::MoveSpeed
+            | StatKind::Damage
+            | StatKind::BulletSpeed
+            | StatKind::BulletLifetime
+            | StatKind::KickSpeed
+            | StatKind::MagnetRange
+            | StatKind::MaxBullets
+            | StatKind::AuraCapacity
+            | StatKind::AuraRegen
+            | StatKind::AuraRadius
+            | StatKind::DashCharges
+            | StatKind::DashDistance
+            | StatKind::BulletCost => StatKind::ALL.contains(&stat),
+        }
+    }
+
+    #[test]
+    fn every_stat_has_a_slot() {
+        let stats = Stats::player();
+        for (i, stat) in StatKind::ALL.into_iter().enumerate() {
+            assert!(is_listed(stat));
+            assert_eq!(stat as usize, i, "{stat:?} is out of order in StatKind::ALL");
+            assert!(stats.get(stat) > 0.0, "{stat:?} has no base value");
+        }
+    }
+}
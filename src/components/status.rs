@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Mỗi loại hiệu ứng giữ tối đa bấy nhiêu stack cùng lúc
+const MAX_STACKS_PER_KIND: usize = 5;
+// Freeze chồng nhiều lớp cũng không làm mục tiêu đứng im hẳn
+const MIN_FROZEN_SPEED: f32 = 0.2;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatusKind {
+    Burn,
+    Freeze,
+    Knockback,
+    Stun,
+}
+
+impl StatusKind {
+    pub fn tint(self) -> Option<Color> {
+        match self {
+            StatusKind::Burn => Some(Color::srgb(1.0, 0.45, 0.2)),
+            StatusKind::Freeze => Some(Color::srgb(0.45, 0.75, 1.0)),
+            StatusKind::Stun => Some(Color::srgb(1.0, 1.0, 0.4)),
+            StatusKind::Knockback => None,
+        }
+    }
+}
+
+/// Hiệu ứng gắn lên mục tiêu khi bị trúng đòn.
+/// `potency` tuỳ loại: Burn = sát thương mỗi giây, Freeze = tỉ lệ giảm tốc,
+/// Knockback = vận tốc đẩy ban đầu, Stun không dùng.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub duration: f32,
+    pub potency: f32,
+}
+
+impl StatusEffect {
+    pub const fn new(kind: StatusKind, duration: f32, potency: f32) -> Self {
+        Self { kind, duration, potency }
+    }
+
+    // Mua thêm cùng loại ở shop thì cộng dồn độ mạnh và lấy thời gian dài hơn
+    pub fn merge(&mut self, other: &StatusEffect) {
+        self.potency += other.potency;
+        self.duration = self.duration.max(other.duration);
+    }
+}
+
+pub struct StatusStack {
+    pub kind: StatusKind,
+    pub potency: f32,
+    // Hướng đẩy của Knockback
+    pub direction: Vec2,
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct StatusEffects {
+    pub stacks: Vec<StatusStack>,
+    // Màu gốc để trả lại khi hết hiệu ứng, giống HitFlash
+    pub original_color: Color,
+    // Tốc độ gốc của quái, lưu ở lần tick đầu tiên để Freeze / Stun nhân vào
+    pub base_speed: Option<f32>,
+}
+
+impl StatusEffects {
+    pub fn new(original_color: Color) -> Self {
+        Self {
+            stacks: Vec::new(),
+            original_color,
+            base_speed: None,
+        }
+    }
+
+    pub fn apply(&mut self, effect: &StatusEffect, direction: Vec2) {
+        let stack = StatusStack {
+            kind: effect.kind,
+            potency: effect.potency,
+            direction,
+            timer: Timer::from_seconds(effect.duration, TimerMode::Once),
+        };
+
+        // Đủ stack rồi thì thay stack sắp hết hạn nhất
+        let same_kind = self.stacks.iter().filter(|s| s.kind == effect.kind).count();
+        if same_kind < MAX_STACKS_PER_KIND {
+            self.stacks.push(stack);
+        } else if let Some(oldest) = self
+            .stacks
+            .iter_mut()
+            .filter(|s| s.kind == effect.kind)
+            .min_by(|a, b| a.timer.remaining_secs().total_cmp(&b.timer.remaining_secs()))
+        {
+            *oldest = stack;
+        }
+    }
+
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        for stack in self.stacks.iter_mut() {
+            stack.timer.tick(delta);
+        }
+        self.stacks.retain(|s| !s.timer.finished());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stacks.is_empty()
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.stacks.iter().any(|s| s.kind == StatusKind::Stun)
+    }
+
+    pub fn burn_per_second(&self) -> f32 {
+        self.stacks
+            .iter()
+            .filter(|s| s.kind == StatusKind::Burn)
+            .map(|s| s.potency)
+            .sum()
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_stunned() {
+            return 0.0;
+        }
+        let slowed: f32 = self
+            .stacks
+            .iter()
+            .filter(|s| s.kind == StatusKind::Freeze)
+            .map(|s| 1.0 - s.potency.clamp(0.0, 1.0))
+            .product();
+        slowed.max(MIN_FROZEN_SPEED)
+    }
+
+    // Lực đẩy giảm dần tuyến tính theo thời gian còn lại
+    pub fn knockback_velocity(&self) -> Vec2 {
+        self.stacks
+            .iter()
+            .filter(|s| s.kind == StatusKind::Knockback)
+            .map(|s| s.direction * s.potency * s.timer.fraction_remaining())
+            .sum()
+    }
+
+    // Ưu tiên màu Stun > Freeze > Burn
+    pub fn tint(&self) -> Option<Color> {
+        [StatusKind::Stun, StatusKind::Freeze, StatusKind::Burn]
+            .into_iter()
+            .find(|kind| self.stacks.iter().any(|s| s.kind == *kind))
+            .and_then(StatusKind::tint)
+    }
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+// Các sự kiện gameplay để relic (và hệ thống khác) bám vào mà không phải
+// sửa trực tiếp từng system va chạm.
+
+#[derive(Event)]
+pub struct BulletBounced {
+    pub owner: Option<Entity>,
+    pub position: Vec2,
+    pub direction: Vec2,
+    pub bounces: u32,
+}
+
+#[derive(Event)]
+pub struct EnemyKilled {
+    pub position: Vec2,
+    pub is_boss: bool,
+}
+
+#[derive(Event)]
+pub struct CoinCollected {
+    pub position: Vec2,
+    pub value: u32,
+}
+
+#[derive(Event)]
+pub struct PlayerDamaged {
+    pub position: Vec2,
+    pub amount: f32,
+}
+
+pub struct GameEventsPlugin;
+
+impl Plugin for GameEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BulletBounced>()
+           .add_event::<EnemyKilled>()
+           .add_event::<CoinCollected>()
+           .add_event::<PlayerDamaged>();
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use super::level_assets::WORLD_TILE_SIZE;
+
+#[derive(Resource, Clone)]
+pub struct LevelGrid {
+    pub width: usize,
+    pub height: usize,
+    // Toạ độ world của tâm ô (0, 0) - góc dưới trái của map
+    pub origin: Vec2,
+    pub walls: Vec<bool>,
+}
+
+impl LevelGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let origin = Vec2::new(
+            -(width as f32 * WORLD_TILE_SIZE) / 2.0 + WORLD_TILE_SIZE / 2.0,
+            -(height as f32 * WORLD_TILE_SIZE) / 2.0 + WORLD_TILE_SIZE / 2.0,
+        );
+
+        Self {
+            width,
+            height,
+            origin,
+            walls: vec![false; width * height],
+        }
+    }
+
+    pub fn index(&self, tile: IVec2) -> Option<usize> {
+        if tile.x < 0 || tile.y < 0 || tile.x as usize >= self.width || tile.y as usize >= self.height {
+            return None;
+        }
+        Some(tile.y as usize * self.width + tile.x as usize)
+    }
+
+    pub fn world_to_tile(&self, pos: Vec2) -> IVec2 {
+        ((pos - self.origin) / WORLD_TILE_SIZE).round().as_ivec2()
+    }
+
+    pub fn tile_to_world(&self, tile: IVec2) -> Vec2 {
+        self.origin + tile.as_vec2() * WORLD_TILE_SIZE
+    }
+
+    pub fn set_wall(&mut self, tile: IVec2) {
+        if let Some(i) = self.index(tile) {
+            self.walls[i] = true;
+        }
+    }
+
+    /// Ô nằm ngoài map cũng được tính là tường.
+    pub fn is_wall(&self, tile: IVec2) -> bool {
+        match self.index(tile) {
+            Some(i) => self.walls[i],
+            None => true,
+        }
+    }
+
+    /// Hộp `half_size` đặt tại `pos` có chạm ô tường nào không.
+    pub fn overlaps_wall(&self, pos: Vec2, half_size: Vec2) -> bool {
+        let min = self.world_to_tile(pos - half_size + Vec2::ONE);
+        let max = self.world_to_tile(pos + half_size - Vec2::ONE);
+        (min.y..=max.y).any(|y| (min.x..=max.x).any(|x| self.is_wall(IVec2::new(x, y))))
+    }
+
+    /// Lực đẩy ra khỏi các ô tường xung quanh, dùng cho local steering
+    /// để quái trượt dọc góc tường thay vì cứ húc vào.
+    pub fn wall_repulsion(&self, pos: Vec2, half_size: Vec2) -> Vec2 {
+        let clearance = half_size.max_element() + WORLD_TILE_SIZE * 0.25;
+        let reach = (clearance / WORLD_TILE_SIZE).ceil() as i32 + 1;
+        let center = self.world_to_tile(pos);
+        let half_tile = Vec2::splat(WORLD_TILE_SIZE / 2.0);
+
+        let mut push = Vec2::ZERO;
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let tile = center + IVec2::new(dx, dy);
+                if !self.is_wall(tile) {
+                    continue;
+                }
+
+                let tile_pos = self.tile_to_world(tile);
+                let closest = pos.clamp(tile_pos - half_tile, tile_pos + half_tile);
+                let offset = pos - closest;
+                let distance = offset.length();
+
+                if distance > 0.0 && distance < clearance {
+                    push += offset / distance * (1.0 - distance / clearance);
+                }
+            }
+        }
+        push
+    }
+}
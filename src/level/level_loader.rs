@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use crate::components::collider::{Collider, Wall};
 use super::level_assets::*;
 use super::tile_config::TILE_CONFIGS;
+use super::level_grid::LevelGrid;
 use crate::resources::level::LevelManager;
 
 #[derive(Component)]
@@ -36,9 +37,16 @@ pub fn spawn_level_from_image(
     };
 
     info!("Spawning Map for Level {}", level_manager.current_level);
+
+    let mut grid = LevelGrid::new(
+        bg_image.texture_descriptor.size.width as usize,
+        bg_image.texture_descriptor.size.height as usize,
+    );
     
-    spawn_layer(&mut commands, &assets, bg_image);
-    spawn_layer(&mut commands, &assets, track_image);
+    spawn_layer(&mut commands, &assets, bg_image, &mut grid);
+    spawn_layer(&mut commands, &assets, track_image, &mut grid);
+
+    commands.insert_resource(grid);
 }
 
 pub fn despawn_map(
@@ -48,6 +56,7 @@ pub fn despawn_map(
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
     }
+    commands.remove_resource::<LevelGrid>();
     info!("Map Despawned");
 }
 
@@ -55,6 +64,7 @@ fn spawn_layer(
     commands: &mut Commands,
     assets: &LevelAssets,
     image: &Image,
+    grid: &mut LevelGrid,
 ) {
     let width = image.texture_descriptor.size.width;
     let height = image.texture_descriptor.size.height;
@@ -102,6 +112,7 @@ fn spawn_layer(
 
             if cfg.has_collision {
                 entity.insert((Wall, Collider::new(WORLD_TILE_SIZE, WORLD_TILE_SIZE)));
+                grid.set_wall(IVec2::new(x as i32, (height - 1 - y) as i32));
             }
         }
     }
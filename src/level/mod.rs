@@ -1,3 +1,4 @@
 pub mod level_assets;
 pub mod level_loader;
 pub mod tile_config;
+pub mod level_grid;
@@ -1,5 +1,6 @@
+use bevy::audio::AudioPlugin;
 use bevy::prelude::*;
-use bevy::window::WindowResolution;
+use bevy::transform::TransformSystem;
 
 mod states;
 mod components; 
@@ -8,31 +9,54 @@ mod utils;
 mod level;
 mod resources;
 mod vfx;
+mod events;
 
 use vfx::VfxPlugin;
+use events::GameEventsPlugin;
 use states::AppState;
-use components::player::{Player, MovementStats};
-use components::stats::PlayerStats;
+use components::player::{Player, AuraEnergy, Dash};
+use components::power_up::ActivePowerUps;
+use components::stats::{PlayerStats, StatKind, Stats};
 use components::collider::Collider;
 use components::enemy::Health; 
 
 use resources::level::LevelManager;
 use resources::game_stats::GameStats;
+use resources::flow_field::FlowField;
+use resources::game_config::{CrowdSettings, ParrySettings, PowerUpTable};
+use resources::enemy_archetypes::{EnemyRoster, EnemyAssets, setup_enemy_archetypes, fallback_enemy_roster, prepare_enemy_assets};
+use resources::shop_catalog::{ShopCatalog, PurchaseHistory, setup_shop_catalog};
+use resources::run_rng::RunRng;
+use resources::relics::{RelicInventory, KillCombo};
+use resources::save_game::SaveSlot;
+use resources::profile::Profile;
+use resources::settings::Settings;
+use resources::pause::{PauseState, game_running};
+use utils::ron_loader::RonAssetLoader;
 use resources::sound::{setup_sound_assets, start_background_music_when_ready, stop_background_music};
 
 use systems::{
-    aura::{spawn_aura, aura_visual_system, aura_logic_system},
-    movement::player_movement,
-    ui::{setup_ui, update_ui, check_game_over, update_wave_ui, update_timer_ui, cleanup_game_ui}, 
-    shooting::{setup_bullet_assets, spawn_bullet, move_and_animate_bullet, cleanup_bullet_bounds},
-    ricochet::{bullet_ricochet, player_wall_collision, enemy_player_collision, enemy_wall_collision, bullet_enemy_collision},
-    enemy_ai::{enemy_movement, animate_enemies},
+    aura::{spawn_aura, aura_visual_system, aura_logic_system, bullet_recall_system},
+    movement::{player_movement, player_dash_input},
+    ui::{setup_ui, update_ui, check_game_over, update_wave_ui, update_timer_ui, cleanup_game_ui, update_boss_bar, update_dash_ui, update_aura_bar, update_relic_ui, update_power_up_hud}, 
+    boss::{boss_behaviour, cleanup_shockwaves, update_weak_points},
+    status::{tick_status_effects, clear_status_effects},
+    stats::update_stats,
+    power_up::{update_power_up_pickups, collect_power_ups, tick_power_ups, slow_field_system, clear_power_ups},
+    shooting::{setup_bullet_assets, spawn_bullet, move_and_animate_bullet, cleanup_bullet_bounds, enemy_ranged_attack},
+    ricochet::{bullet_ricochet, player_wall_collision, enemy_wall_collision, bullet_enemy_collision, player_damage_systems, update_invulnerability},
+    enemy_ai::{enemy_movement, animate_enemies, update_flow_field, enemy_soft_push, update_shield_visuals},
     particle::update_particles,
-    gameplay::{player_collect_coin, reset_player_position, reset_game_state, despawn_all_enemies, cleanup_level_items},
+    gameplay::{player_collect_coin, update_coins, merge_coins, player_collect_dropped_bullet, detect_ammo_softlock, reset_player_position, reset_game_state, despawn_all_enemies, cleanup_level_items},
     timer::update_timer,
     wave::wave_system,
     menu::{setup_menu, menu_action, cleanup_menu},
     endgame::{setup_game_over, setup_victory, endgame_action, cleanup_endgame},
+    relic::{relic_bounce_shards, relic_kill_combo, relic_coin_blast, relic_spite_knockback, drop_boss_relic, collect_relic_pickup},
+    profile::{count_kills, award_run_rewards, refresh_unlock_list, profile_action},
+    settings::{settings_action, update_settings_labels, apply_audio_settings, scale_sfx_volume, apply_window_settings},
+    pause::{toggle_pause, pause_action, clear_pause},
+    save::{save_run, load_save_slot, resume_saved_run, clear_run_save},
     shop::{setup_shop, shop_interaction, shop_next_level, cleanup_shop}, 
 };
 
@@ -43,38 +67,70 @@ const PLAYER_SPRITE_PATH: &str = "sprites/player/player.png";
 const TILE_SIZE: f32 = 48.0; 
 
 fn main() {
+    // Đọc cài đặt trước để tạo cửa sổ đúng chế độ và độ phân giải ngay từ đầu
+    let settings = Settings::load();
+
     App::new()
         // PLUGINS & WINDOW 
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()).set(WindowPlugin {
             primary_window: Some(Window {
                 title: "Last Ricochet".into(),
-                resolution: WindowResolution::new(1280.0, 720.0),
+                resolution: settings.window_resolution(),
+                mode: settings.display_mode.window_mode(),
+                present_mode: settings.present_mode(),
                 ..default()
             }),
             ..default()
+        }).set(AudioPlugin {
+            global_volume: GlobalVolume::new(settings.master_volume),
+            ..default()
         }))
         .add_plugins(VfxPlugin)
+        .add_plugins(GameEventsPlugin)
+        .init_asset::<EnemyRoster>()
+        .register_asset_loader(RonAssetLoader::<EnemyRoster>::new(&["enemies.ron"]))
+        .init_asset::<ShopCatalog>()
+        .register_asset_loader(RonAssetLoader::<ShopCatalog>::new(&["catalog.ron"]))
 
         // STATES & RESOURCES
         .init_state::<AppState>()
         .init_resource::<LevelManager>()
         .init_resource::<GameStats>()
+        .init_resource::<FlowField>()
+        .init_resource::<CrowdSettings>()
+        .init_resource::<ParrySettings>()
+        .init_resource::<PowerUpTable>()
+        .init_resource::<EnemyAssets>()
+        .init_resource::<PurchaseHistory>()
+        .init_resource::<RunRng>()
+        .init_resource::<RelicInventory>()
+        .init_resource::<KillCombo>()
+        .init_resource::<SaveSlot>()
+        .insert_resource(Profile::load())
+        .insert_resource(settings)
+        .init_resource::<PauseState>()
         
         // STARTUP
-        .add_systems(Startup, (setup, setup_bullet_assets, setup_level_assets, setup_game_assets, setup_sound_assets))
+        .add_systems(Startup, (setup, setup_bullet_assets, setup_level_assets, setup_game_assets, setup_sound_assets, setup_enemy_archetypes, setup_shop_catalog))
+
+        .add_systems(Update, (fallback_enemy_roster, prepare_enemy_assets).chain())
+
+        // SETTINGS (mọi state)
+        .add_systems(Update, (settings_action, update_settings_labels, apply_audio_settings, apply_window_settings))
+        .add_systems(PostUpdate, scale_sfx_volume.before(TransformSystem::TransformPropagate))
 
         // STATE: MENU
-        .add_systems(OnEnter(AppState::Menu), setup_menu)
-        .add_systems(Update, menu_action.run_if(in_state(AppState::Menu)))
+        .add_systems(OnEnter(AppState::Menu), (load_save_slot, setup_menu).chain())
+        .add_systems(Update, (menu_action, refresh_unlock_list, profile_action).run_if(in_state(AppState::Menu)))
         .add_systems(
         Update,
         start_background_music_when_ready.run_if(in_state(AppState::Menu))
         )
-        .add_systems(OnExit(AppState::Menu), (cleanup_menu, reset_game_state))
+        .add_systems(OnExit(AppState::Menu), (cleanup_menu, reset_game_state, resume_saved_run.after(reset_game_state)))
         
         // STATE: PLAYING
         .add_systems(OnEnter(AppState::Playing), (spawn_level_from_image, setup_ui, reset_player_position))
-        .add_systems(OnExit(AppState::Playing), (despawn_map, cleanup_game_ui, despawn_all_enemies, cleanup_level_items))
+        .add_systems(OnExit(AppState::Playing), (despawn_map, cleanup_game_ui, despawn_all_enemies, cleanup_level_items, cleanup_shockwaves, clear_status_effects, clear_power_ups, clear_pause))
 
         // Physics (Playing)
         .add_systems(
@@ -82,7 +138,10 @@ fn main() {
             (
                 player_movement, 
                 player_wall_collision,
+                update_flow_field,
                 enemy_movement,
+                boss_behaviour,
+                enemy_soft_push,
                 enemy_wall_collision,
             ).chain()
                 .run_if(in_state(AppState::Playing))
@@ -102,7 +161,8 @@ fn main() {
                 cleanup_bullet_bounds,
                 bullet_ricochet,
                 bullet_enemy_collision, 
-                enemy_player_collision,
+                player_damage_systems(),
+                enemy_ranged_attack,
                 spawn_aura,
                 aura_visual_system,
                 aura_logic_system,
@@ -110,11 +170,55 @@ fn main() {
                 wave_system,
                 player_collect_coin,
                 update_particles,
-            ).run_if(in_state(AppState::Playing))
+            ).run_if(in_state(AppState::Playing).and_then(game_running))
         )
+        .add_systems(
+            Update,
+            (
+                update_shield_visuals,
+                update_weak_points,
+                update_boss_bar,
+                tick_status_effects.before(bullet_enemy_collision),
+                update_invulnerability,
+                player_dash_input,
+                update_dash_ui,
+                update_aura_bar,
+                bullet_recall_system.before(move_and_animate_bullet).before(bullet_ricochet).before(aura_logic_system),
+                player_collect_dropped_bullet,
+                detect_ammo_softlock,
+                update_coins.before(player_collect_coin),
+                merge_coins,
+            ).run_if(in_state(AppState::Playing).and_then(game_running))
+        )
+        .add_systems(
+            Update,
+            (
+                relic_bounce_shards.after(bullet_ricochet),
+                relic_kill_combo,
+                relic_coin_blast.after(player_collect_coin),
+                relic_spite_knockback,
+                drop_boss_relic,
+                collect_relic_pickup,
+                update_relic_ui,
+                update_stats,
+                count_kills,
+            ).run_if(in_state(AppState::Playing).and_then(game_running))
+        )
+        .add_systems(
+            Update,
+            (
+                update_power_up_pickups,
+                collect_power_ups,
+                tick_power_ups,
+                slow_field_system,
+                update_power_up_hud,
+            ).run_if(in_state(AppState::Playing).and_then(game_running))
+        )
+
+        .add_systems(Update, (toggle_pause, pause_action).run_if(in_state(AppState::Playing)))
 
         // STATE: BUFF SCREEN
-        .add_systems(OnEnter(AppState::BuffScreen), (setup_shop, despawn_map, setup_ui))
+        .add_systems(OnEnter(AppState::BuffScreen), (save_run.before(setup_shop), setup_shop, despawn_map, setup_ui))
         
         .add_systems(
             Update,
@@ -123,18 +227,20 @@ fn main() {
                 shop_interaction,  
                 shop_next_level,   
                 update_ui,     
+                update_invulnerability,
+                update_stats,
             ).run_if(in_state(AppState::BuffScreen))
         )
 
         .add_systems(OnExit(AppState::BuffScreen), (cleanup_shop, cleanup_game_ui))
 
         // STATE: VICTORY
-        .add_systems(OnEnter(AppState::Victory), (setup_victory, stop_background_music)) 
+        .add_systems(OnEnter(AppState::Victory), (award_run_rewards.before(setup_victory), setup_victory, stop_background_music, clear_run_save)) 
         .add_systems(Update, endgame_action.run_if(in_state(AppState::Victory)))
         .add_systems(OnExit(AppState::Victory), (cleanup_endgame, reset_game_state)) 
 
         //STATE: GAME OVER 
-        .add_systems(OnEnter(AppState::GameOver), (setup_game_over, stop_background_music))
+        .add_systems(OnEnter(AppState::GameOver), (award_run_rewards.before(setup_game_over), setup_game_over, stop_background_music, clear_run_save))
         .add_systems(Update, endgame_action.run_if(in_state(AppState::GameOver)))
         .add_systems(OnExit(AppState::GameOver), (cleanup_endgame, reset_game_state))
 
@@ -149,6 +255,8 @@ fn setup(
     commands.spawn(Camera2dBundle::default());
 
     let texture = asset_server.load(PLAYER_SPRITE_PATH);
+    let stats = Stats::player();
+    let max_hp = stats.get(StatKind::MaxHp);
     let layout = TextureAtlasLayout::from_grid(UVec2::splat(TILE_SIZE as u32), 6, 1, None, None);
     let texture_atlas_layout = texture_atlas_layouts.add(layout); 
     
@@ -163,9 +271,12 @@ fn setup(
             index: 0,
         },
         Player,
-        MovementStats::default(),
+        Dash::full(&stats),
+        AuraEnergy::full(&stats),
         PlayerStats::default(),
-        Health::new(5.0), 
+        ActivePowerUps::default(),
+        stats,
+        Health::new(max_hp),
         Collider::new(30.0, 33.0),
     ));
 }
\ No newline at end of file
@@ -0,0 +1,305 @@
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::resources::game_config::EnemyType;
+use crate::resources::loot::LootTable;
+use crate::utils::ron_loader::Validate;
+
+pub const ENEMY_ROSTER_PATH: &str = "data/golems.enemies.ron";
+// Bản roster đóng gói sẵn trong binary, dùng khi file trong `assets` hỏng
+const BUILTIN_ROSTER: &str = include_str!("../../assets/data/golems.enemies.ron");
+const FALLBACK_ANIMATION_FPS: f32 = 10.0;
+const FALLBACK_HP: f32 = 1.0;
+const FALLBACK_FIRE_INTERVAL: f32 = 2.0;
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum Behaviour {
+    Chase,
+    Ranged { fire_interval: f32, preferred_distance: f32 },
+    Shielded,
+    Splitter { child: String, min_children: u32, max_children: u32 },
+    Boss { summon: String },
+}
+
+impl Behaviour {
+    // Archetype khác mà behaviour này sinh ra lúc chơi
+    fn spawns(&self) -> Option<&str> {
+        match self {
+            Behaviour::Splitter { child, .. } => Some(child),
+            Behaviour::Boss { summon } => Some(summon),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnemyArchetype {
+    pub hp: f32,
+    pub speed: f32,
+    pub damage: f32,
+    pub scale: f32,
+    pub collider: (f32, f32),
+    pub tint: (f32, f32, f32),
+    pub sprite_sheet: String,
+    pub frame_size: u32,
+    pub frame_count: usize,
+    pub animation_fps: f32,
+    // Dùng chung giữa mọi con quái cùng archetype, spawn không phải clone bảng
+    pub loot: Arc<LootTable>,
+    pub behaviour: Behaviour,
+}
+
+impl EnemyArchetype {
+    pub fn color(&self) -> Color {
+        Color::srgb(self.tint.0, self.tint.1, self.tint.2)
+    }
+}
+
+/// Toàn bộ archetype quái, đọc từ file RON trong `assets/data` để designer
+/// thêm quái mới mà không cần sửa code.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct EnemyRoster {
+    pub archetypes: HashMap<String, EnemyArchetype>,
+}
+
+impl EnemyRoster {
+    pub fn builtin() -> Self {
+        let mut roster: Self = ron::from_str(BUILTIN_ROSTER).expect("built-in enemy roster must parse");
+        roster.validate();
+        roster
+    }
+}
+
+impl Validate for EnemyRoster {
+    fn validate(&mut self) {
+        for (id, archetype) in self.archetypes.iter_mut() {
+            // Máu <= 0 thì quái sinh ra đã "chết" mà không bao giờ đi qua đường giết
+            if !(archetype.hp > 0.0 && archetype.hp.is_finite()) {
+                error!("Enemy '{id}' has hp {}, using {FALLBACK_HP}", archetype.hp);
+                archetype.hp = FALLBACK_HP;
+            }
+            if archetype.frame_count == 0 {
+                error!("Enemy '{id}' has frame_count 0, using 1");
+                archetype.frame_count = 1;
+            }
+            if !(archetype.animation_fps > 0.0 && archetype.animation_fps.is_finite()) {
+                error!("Enemy '{id}' has animation_fps {}, using {FALLBACK_ANIMATION_FPS}", archetype.animation_fps);
+                archetype.animation_fps = FALLBACK_ANIMATION_FPS;
+            }
+            if let Behaviour::Splitter { min_children, max_children, .. } = &mut archetype.behaviour {
+                if *min_children > *max_children {
+                    error!("Enemy '{id}' has min_children {min_children} > max_children {max_children}, swapping them");
+                    std::mem::swap(min_children, max_children);
+                }
+            }
+            if let Behaviour::Ranged { fire_interval, .. } = &mut archetype.behaviour {
+                if !(*fire_interval > 0.0 && fire_interval.is_finite()) {
+                    error!("Enemy '{id}' has fire_interval {fire_interval}, using {FALLBACK_FIRE_INTERVAL}");
+                    *fire_interval = FALLBACK_FIRE_INTERVAL;
+                }
+            }
+        }
+
+        // Gõ sai id thì splitter / boss sẽ không sinh ra gì, coi như cả file hỏng
+        let mut has_missing = false;
+        for (id, archetype) in &self.archetypes {
+            if let Some(target) = archetype.behaviour.spawns().filter(|target| !self.archetypes.contains_key(*target)) {
+                error!("Enemy '{id}' spawns unknown archetype '{target}'");
+                has_missing = true;
+            }
+        }
+        if has_missing {
+            error!("Enemy roster has dangling archetype references, using the built-in one");
+            *self = Self::builtin();
+        }
+    }
+}
+
+pub struct EnemySprite {
+    pub texture: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+/// Texture và atlas layout của từng archetype, tạo một lần khi roster load
+/// xong rồi dùng chung cho mọi lần spawn (giống `BulletAssets`).
+#[derive(Resource, Default)]
+pub struct EnemyAssets {
+    pub sprites: HashMap<String, EnemySprite>,
+}
+
+impl EnemyAssets {
+    pub fn from_roster(roster: &EnemyRoster, asset_server: &AssetServer, layouts: &mut Assets<TextureAtlasLayout>) -> Self {
+        let sprites = roster
+            .archetypes
+            .iter()
+            .map(|(id, archetype)| {
+                let layout = TextureAtlasLayout::from_grid(
+                    UVec2::splat(archetype.frame_size),
+                    archetype.frame_count as u32,
+                    1,
+                    None,
+                    None,
+                );
+                let sprite = EnemySprite {
+                    texture: asset_server.load(archetype.sprite_sheet.clone()),
+                    layout: layouts.add(layout),
+                };
+                (id.clone(), sprite)
+            })
+            .collect();
+        Self { sprites }
+    }
+}
+
+#[derive(Resource)]
+pub struct EnemyArchetypes {
+    pub roster: Handle<EnemyRoster>,
+}
+
+impl EnemyArchetypes {
+    pub fn get<'a>(&self, rosters: &'a Assets<EnemyRoster>, enemy_type: &EnemyType) -> Option<&'a EnemyArchetype> {
+        rosters.get(&self.roster)?.archetypes.get(&enemy_type.0)
+    }
+
+    pub fn is_loaded(&self, rosters: &Assets<EnemyRoster>) -> bool {
+        rosters.contains(&self.roster)
+    }
+}
+
+pub fn setup_enemy_archetypes(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(EnemyArchetypes {
+        roster: asset_server.load(ENEMY_ROSTER_PATH),
+    });
+}
+
+/// Roster không load được (thiếu file, sai cú pháp) thì dùng bản đóng gói
+/// sẵn, để wave không đứng chờ mãi.
+pub fn fallback_enemy_roster(
+    asset_server: Res<AssetServer>,
+    mut archetypes: ResMut<EnemyArchetypes>,
+    mut rosters: ResMut<Assets<EnemyRoster>>,
+) {
+    let Some(LoadState::Failed(err)) = asset_server.get_load_state(&archetypes.roster) else { return };
+
+    error!("Could not load enemy roster, using the built-in one: {err}");
+    archetypes.roster = rosters.add(EnemyRoster::builtin());
+}
+
+pub fn prepare_enemy_assets(
+    mut events: EventReader<AssetEvent<EnemyRoster>>,
+    archetypes: Res<EnemyArchetypes>,
+    rosters: Res<Assets<EnemyRoster>>,
+    asset_server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut enemy_assets: ResMut<EnemyAssets>,
+) {
+    let roster_changed = events.read().any(|event| match event {
+        // Roster dự phòng được thêm thẳng vào Assets nên chỉ có `Added`
+        AssetEvent::Added { id } | AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => {
+            *id == archetypes.roster.id()
+        }
+        _ => false,
+    });
+    if !roster_changed {
+        return;
+    }
+    let Some(roster) = rosters.get(&archetypes.roster) else { return };
+
+    *enemy_assets = EnemyAssets::from_roster(roster, &asset_server, &mut layouts);
+    info!("Prepared sprites for {} enemy archetypes", enemy_assets.sprites.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Các giá trị từng làm panic lúc spawn / animate phải được sửa ngay khi load
+    #[test]
+    fn validate_fixes_values_that_would_panic() {
+        let mut roster: EnemyRoster = ron::from_str(r#"(
+            archetypes: {
+                "broken": (
+                    hp: 1.0, speed: 1.0, damage: 1.0, scale: 1.0,
+                    collider: (1.0, 1.0), tint: (1.0, 1.0, 1.0),
+                    sprite_sheet: "sprites/enemy/Golem_Armor_Run.png",
+                    frame_size: 64, frame_count: 0, animation_fps: 0.0,
+                    loot: (entries: []),
+                    behaviour: Splitter(child: "broken", min_children: 3, max_children: 1),
+                ),
+            },
+        )"#).unwrap();
+        roster.validate();
+
+        let archetype = &roster.archetypes["broken"];
+        assert_eq!(archetype.frame_count, 1);
+        assert_eq!(archetype.animation_fps, FALLBACK_ANIMATION_FPS);
+        assert!(matches!(archetype.behaviour, Behaviour::Splitter { min_children: 1, max_children: 3, .. }));
+    }
+
+    fn single_archetype(hp: f32, behaviour: &str) -> EnemyRoster {
+        ron::from_str(&format!(r#"(
+            archetypes: {{
+                "broken": (
+                    hp: {hp:?}, speed: 1.0, damage: 1.0, scale: 1.0,
+                    collider: (1.0, 1.0), tint: (1.0, 1.0, 1.0),
+                    sprite_sheet: "sprites/enemy/Golem_Armor_Run.png",
+                    frame_size: 64, frame_count: 4, animation_fps: 6.0,
+                    loot: (entries: []),
+                    behaviour: {behaviour},
+                ),
+            }},
+        )"#)).unwrap()
+    }
+
+    // fire_interval 0 làm gen_range(0.0..0.0) panic lúc spawn
+    #[test]
+    fn validate_fixes_non_positive_fire_interval() {
+        for interval in ["0.0", "-1.5"] {
+            let mut roster = single_archetype(1.0, &format!("Ranged(fire_interval: {interval}, preferred_distance: 200.0)"));
+            roster.validate();
+            assert!(matches!(roster.archetypes["broken"].behaviour, Behaviour::Ranged { fire_interval, .. } if fire_interval == FALLBACK_FIRE_INTERVAL));
+        }
+    }
+
+    #[test]
+    fn validate_fixes_non_positive_hp() {
+        for hp in [0.0, -3.0] {
+            let mut roster = single_archetype(hp, "Chase");
+            roster.validate();
+            assert_eq!(roster.archetypes["broken"].hp, FALLBACK_HP);
+        }
+    }
+
+    #[test]
+    fn unknown_spawn_reference_falls_back_to_builtin_roster() {
+        let mut roster: EnemyRoster = ron::from_str(r#"(
+            archetypes: {
+                "splitter": (
+                    hp: 1.0, speed: 1.0, damage: 1.0, scale: 1.0,
+                    collider: (1.0, 1.0), tint: (1.0, 1.0, 1.0),
+                    sprite_sheet: "sprites/enemy/Golem_Armor_Run.png",
+                    frame_size: 64, frame_count: 4, animation_fps: 6.0,
+                    loot: (entries: []),
+                    behaviour: Splitter(child: "splitlnig", min_children: 1, max_children: 2),
+                ),
+            },
+        )"#).unwrap();
+        roster.validate();
+
+        assert_eq!(roster.archetypes.len(), EnemyRoster::builtin().archetypes.len());
+        assert!(roster.archetypes.contains_key("splitling"));
+    }
+
+    #[test]
+    fn builtin_roster_has_every_archetype() {
+        let roster = EnemyRoster::builtin();
+        for id in ["normal", "tank", "speed", "ranged", "shielded", "splitter", "splitling", "boss"] {
+            assert!(roster.archetypes.contains_key(id), "missing '{id}'");
+        }
+    }
+}
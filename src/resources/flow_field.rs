@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use crate::level::level_grid::LevelGrid;
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+const NEIGHBOURS: [IVec2; 8] = [
+    IVec2::new(1, 0),
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(0, -1),
+    IVec2::new(1, 1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+];
+
+/// Flow field dẫn mọi quái về phía player. Chỉ tính lại khi player đổi ô
+/// hoặc map thay đổi, nên chi phí không phụ thuộc vào số lượng quái.
+#[derive(Resource, Default)]
+pub struct FlowField {
+    pub target: Option<IVec2>,
+    pub grid: Option<LevelGrid>,
+    pub costs: Vec<u32>,
+    pub next_tile: Vec<Option<IVec2>>,
+}
+
+impl FlowField {
+    pub fn rebuild(&mut self, grid: &LevelGrid, target: IVec2) {
+        let cell_count = grid.width * grid.height;
+        self.target = Some(target);
+        self.costs = vec![u32::MAX; cell_count];
+        self.next_tile = vec![None; cell_count];
+
+        let Some(target_index) = grid.index(target) else {
+            self.grid = Some(grid.clone());
+            return;
+        };
+
+        let mut open = BinaryHeap::new();
+        self.costs[target_index] = 0;
+        open.push(Reverse((0, target_index)));
+
+        while let Some(Reverse((cost, index))) = open.pop() {
+            if cost > self.costs[index] {
+                continue;
+            }
+
+            let tile = IVec2::new((index % grid.width) as i32, (index / grid.width) as i32);
+
+            for offset in NEIGHBOURS {
+                let neighbour = tile + offset;
+                if !can_step(grid, tile, offset) {
+                    continue;
+                }
+                let Some(neighbour_index) = grid.index(neighbour) else { continue };
+
+                let step = if offset.x != 0 && offset.y != 0 { DIAGONAL_COST } else { STRAIGHT_COST };
+                let new_cost = cost + step;
+
+                if new_cost < self.costs[neighbour_index] {
+                    self.costs[neighbour_index] = new_cost;
+                    self.next_tile[neighbour_index] = Some(tile);
+                    open.push(Reverse((new_cost, neighbour_index)));
+                }
+            }
+        }
+
+        self.grid = Some(grid.clone());
+    }
+
+    /// Tâm của ô kế tiếp trên đường về player, hoặc `None` nếu quái đã
+    /// ở cùng ô với player / không có đường đi (khi đó cứ đuổi thẳng).
+    pub fn waypoint(&self, pos: Vec2) -> Option<Vec2> {
+        let grid = self.grid.as_ref()?;
+        let tile = grid.world_to_tile(pos);
+        let index = grid.index(tile)?;
+        let next = self.next_tile[index]?;
+        Some(grid.tile_to_world(next))
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+// Không cho đi chéo cắt góc tường
+fn can_step(grid: &LevelGrid, from: IVec2, offset: IVec2) -> bool {
+    if grid.is_wall(from + offset) {
+        return false;
+    }
+    if offset.x != 0 && offset.y != 0 {
+        return !grid.is_wall(from + IVec2::new(offset.x, 0)) && !grid.is_wall(from + IVec2::new(0, offset.y));
+    }
+    true
+}
@@ -1,9 +1,98 @@
 
-#[derive(Clone, Copy)]
-pub enum EnemyType {
-    Normal, 
-    Tank,   
-    Speed,  
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::components::power_up::PowerUpKind;
+
+#[derive(Resource)]
+pub struct CrowdSettings {
+    pub separation_weight: f32,
+    pub flank_weight: f32,
+    pub soft_push: bool,
+}
+
+impl Default for CrowdSettings {
+    fn default() -> Self {
+        Self {
+            separation_weight: 1.2,
+            flank_weight: 0.6,
+            soft_push: true,
+        }
+    }
+}
+
+/// Canh bấm Space ngay trước khi đạn chạm mép aura để parry hoàn hảo.
+#[derive(Resource)]
+pub struct ParrySettings {
+    // Bấm sớm tối đa bao nhiêu giây trước khi đạn vào aura
+    pub window: f32,
+    pub speed_multiplier: f32,
+    pub damage_multiplier: f32,
+    pub hit_stop: f32,
+    pub flash_duration: f32,
+}
+
+impl ParrySettings {
+    pub fn is_perfect(&self, pressed_at: f32, entered_at: f32) -> bool {
+        let early = entered_at - pressed_at;
+        (0.0..=self.window).contains(&early)
+    }
+}
+
+impl Default for ParrySettings {
+    fn default() -> Self {
+        Self {
+            window: 0.15,
+            speed_multiplier: 1.5,
+            damage_multiplier: 2.0,
+            hit_stop: 0.06,
+            flash_duration: 0.15,
+        }
+    }
+}
+
+/// Trọng số từng loại power-up khi bảng loot của quái rơi ra `LootDrop::PowerUp`.
+#[derive(Resource)]
+pub struct PowerUpTable {
+    pub weights: Vec<(PowerUpKind, u32)>,
+    // Power-up nằm trên sàn bao lâu thì biến mất
+    pub lifetime: f32,
+}
+
+impl PowerUpTable {
+    pub fn roll(&self, rng: &mut impl Rng) -> Option<PowerUpKind> {
+        self.weights
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .ok()
+            .map(|(kind, _)| *kind)
+    }
+}
+
+impl Default for PowerUpTable {
+    fn default() -> Self {
+        Self {
+            weights: vec![
+                (PowerUpKind::RapidFire, 3),
+                (PowerUpKind::DoubleDamage, 3),
+                (PowerUpKind::ShieldBubble, 2),
+                (PowerUpKind::Magnet, 3),
+                (PowerUpKind::SlowField, 2),
+            ],
+            lifetime: 8.0,
+        }
+    }
+}
+
+pub const BOSS_ARCHETYPE: &str = "boss";
+
+// Tham chiếu tới một archetype trong file dữ liệu quái (theo id)
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct EnemyType(pub String);
+
+impl EnemyType {
+    pub fn new(id: &str) -> Self {
+        Self(id.to_string())
+    }
 }
 
 #[derive(Clone)]
@@ -13,6 +102,17 @@ pub struct WaveData {
     pub enemy_type: EnemyType, 
 }
 
+impl WaveData {
+    // Wave boss: chỉ một con boss, kết thúc level
+    pub fn boss() -> Self {
+        Self { enemy_count: 1, spawn_interval: 1.0, enemy_type: EnemyType::new(BOSS_ARCHETYPE) }
+    }
+
+    pub fn is_boss(&self) -> bool {
+        self.enemy_type.0 == BOSS_ARCHETYPE
+    }
+}
+
 pub struct LevelConfig {
     pub waves: Vec<WaveData>,
     pub difficulty_multiplier: f32, 
@@ -24,30 +124,70 @@ impl LevelConfig {
             1 => LevelConfig {
                 difficulty_multiplier: 1.0,
                 waves: vec![
-                    WaveData { enemy_count: 5, spawn_interval: 1.5, enemy_type: EnemyType::Normal },
-                    WaveData { enemy_count: 8, spawn_interval: 1.2, enemy_type: EnemyType::Normal },
+                    WaveData { enemy_count: 5, spawn_interval: 1.5, enemy_type: EnemyType::new("normal") },
+                    WaveData { enemy_count: 8, spawn_interval: 1.2, enemy_type: EnemyType::new("normal") },
+                    WaveData::boss(),
                 ],
             },
             2 => LevelConfig {
                 difficulty_multiplier: 1.5, 
                 waves: vec![
-                    WaveData { enemy_count: 8, spawn_interval: 1.2, enemy_type: EnemyType::Normal },
-                    WaveData { enemy_count: 8, spawn_interval: 1.5, enemy_type: EnemyType::Speed }, 
-                    WaveData { enemy_count: 10, spawn_interval: 1.0, enemy_type: EnemyType::Normal },
+                    WaveData { enemy_count: 8, spawn_interval: 1.2, enemy_type: EnemyType::new("normal") },
+                    WaveData { enemy_count: 8, spawn_interval: 1.5, enemy_type: EnemyType::new("speed") }, 
+                    WaveData { enemy_count: 3, spawn_interval: 2.5, enemy_type: EnemyType::new("splitter") },
+                    WaveData { enemy_count: 4, spawn_interval: 2.0, enemy_type: EnemyType::new("ranged") },
+                    WaveData { enemy_count: 10, spawn_interval: 1.0, enemy_type: EnemyType::new("normal") },
+                    WaveData { enemy_count: 3, spawn_interval: 2.0, enemy_type: EnemyType::new("shielded") },
+                    WaveData::boss(),
                 ],
             },
             3 => LevelConfig {
                 difficulty_multiplier: 2.0,
                 waves: vec![
-                    WaveData { enemy_count: 10, spawn_interval: 1.0, enemy_type: EnemyType::Normal },
-                    WaveData { enemy_count: 8, spawn_interval: 2.0, enemy_type: EnemyType::Tank }, 
-                    WaveData { enemy_count: 25, spawn_interval: 0.8, enemy_type: EnemyType::Speed },
+                    WaveData { enemy_count: 10, spawn_interval: 1.0, enemy_type: EnemyType::new("normal") },
+                    WaveData { enemy_count: 8, spawn_interval: 2.0, enemy_type: EnemyType::new("tank") }, 
+                    WaveData { enemy_count: 6, spawn_interval: 1.5, enemy_type: EnemyType::new("ranged") },
+                    WaveData { enemy_count: 6, spawn_interval: 1.8, enemy_type: EnemyType::new("shielded") },
+                    WaveData { enemy_count: 25, spawn_interval: 0.8, enemy_type: EnemyType::new("speed") },
+                    WaveData { enemy_count: 6, spawn_interval: 2.0, enemy_type: EnemyType::new("splitter") },
+                    WaveData::boss(),
                 ],
             },
             _ => LevelConfig {
                 difficulty_multiplier: 3.0,
-                waves: vec![WaveData { enemy_count: 50, spawn_interval: 0.3, enemy_type: EnemyType::Normal }],
+                waves: vec![WaveData { enemy_count: 50, spawn_interval: 0.3, enemy_type: EnemyType::new("normal") }],
             },
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parry(window: f32) -> ParrySettings {
+        ParrySettings { window, ..default() }
+    }
+
+    #[test]
+    fn parry_inside_window_is_perfect() {
+        let parry = parry(0.25);
+        assert!(parry.is_perfect(2.0, 2.1));
+        // Bấm cùng frame đạn chạm mép aura
+        assert!(parry.is_perfect(2.0, 2.0));
+    }
+
+    #[test]
+    fn parry_outside_window_is_not_perfect() {
+        let parry = parry(0.25);
+        assert!(!parry.is_perfect(2.0, 2.5));
+        // Bấm sau khi đạn đã vào aura
+        assert!(!parry.is_perfect(2.1, 2.0));
+    }
+
+    #[test]
+    fn parry_window_boundary_is_inclusive() {
+        let parry = parry(0.25);
+        assert!(parry.is_perfect(2.0, 2.25));
+        assert!(!parry.is_perfect(2.0, 2.25 + f32::EPSILON * 4.0));
+    }
+}
@@ -4,4 +4,10 @@ use bevy::prelude::*;
 pub struct GameStats {
     pub total_time: f32,
     pub shots_fired: u32,
+    pub parries: u32,
+    pub kills: u32,
+    // Shard hồ sơ nhận được khi run kết thúc
+    pub shards_earned: u32,
+    // Lý do kết thúc run nếu không phải do hết máu
+    pub end_reason: Option<String>,
 }
\ No newline at end of file
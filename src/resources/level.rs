@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use crate::resources::game_config::{LevelConfig, WaveData};
+use crate::resources::profile::Difficulty;
 
 #[derive(Resource)]
 pub struct LevelManager {
@@ -9,6 +10,8 @@ pub struct LevelManager {
    
     pub current_waves_data: Vec<WaveData>, 
     pub difficulty_multiplier: f32,
+    // Độ khó chọn trong hồ sơ, nhân thêm vào multiplier của từng level
+    pub difficulty: Difficulty,
 
    
     pub enemies_spawned: usize,
@@ -25,6 +28,7 @@ impl Default for LevelManager {
             current_wave_index: 0,
             current_waves_data: vec![],
             difficulty_multiplier: 1.0,
+            difficulty: Difficulty::Normal,
             enemies_spawned: 0,
             spawn_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
             level_completed: false,
@@ -42,7 +46,7 @@ impl LevelManager {
         self.current_level = level;
         self.current_wave_index = 0;
         self.current_waves_data = config.waves;
-        self.difficulty_multiplier = config.difficulty_multiplier;
+        self.difficulty_multiplier = config.difficulty_multiplier * self.difficulty.enemy_multiplier();
         
         self.enemies_spawned = 0;
         self.level_completed = false;
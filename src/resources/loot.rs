@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CoinSize {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+impl CoinSize {
+    // Coin gộp lại mang tổng giá trị, hiển thị theo mệnh giá lớn nhất không vượt quá
+    pub fn for_value(value: u32) -> Self {
+        if value >= CoinSize::Gold.value() {
+            CoinSize::Gold
+        } else if value >= CoinSize::Silver.value() {
+            CoinSize::Silver
+        } else {
+            CoinSize::Bronze
+        }
+    }
+
+    pub fn value(self) -> u32 {
+        match self {
+            CoinSize::Bronze => 1,
+            CoinSize::Silver => 3,
+            CoinSize::Gold => 10,
+        }
+    }
+
+    // Dùng chung sprite coin, phân biệt bằng màu và kích thước
+    pub fn tint(self) -> Color {
+        match self {
+            CoinSize::Bronze => Color::srgb(0.85, 0.55, 0.35),
+            CoinSize::Silver => Color::srgb(0.85, 0.9, 1.0),
+            CoinSize::Gold => Color::srgb(1.0, 0.85, 0.2),
+        }
+    }
+
+    pub fn size(self) -> f32 {
+        match self {
+            CoinSize::Bronze => 20.0,
+            CoinSize::Silver => 24.0,
+            CoinSize::Gold => 30.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LootDrop {
+    Nothing,
+    Coin(CoinSize),
+    // Loại power-up bốc tiếp theo trọng số trong `PowerUpTable`
+    PowerUp,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LootEntry {
+    pub drop: LootDrop,
+    pub weight: u32,
+}
+
+fn one_roll() -> u32 {
+    1
+}
+
+/// Bảng rơi đồ của một archetype, khai báo trong file dữ liệu quái.
+#[derive(Deserialize, Clone, Debug)]
+pub struct LootTable {
+    // Số lần bốc theo trọng số mỗi khi quái chết
+    #[serde(default = "one_roll")]
+    pub rolls: u32,
+    pub entries: Vec<LootEntry>,
+    // Luôn rơi, dành cho quái tinh anh và boss
+    #[serde(default)]
+    pub guaranteed: Vec<LootDrop>,
+    // Bốc thêm khi bị giết bằng đạn đã nảy qua tường
+    #[serde(default = "one_roll")]
+    pub ricochet_rolls: u32,
+}
+
+impl LootTable {
+    pub fn roll(&self, rng: &mut impl Rng, ricochet: bool) -> Vec<LootDrop> {
+        let rolls = self.rolls + if ricochet { self.ricochet_rolls } else { 0 };
+
+        let mut drops = self.guaranteed.clone();
+        for _ in 0..rolls {
+            if let Ok(entry) = self.entries.choose_weighted(rng, |entry| entry.weight) {
+                drops.push(entry.drop);
+            }
+        }
+        drops.retain(|drop| *drop != LootDrop::Nothing);
+        drops
+    }
+}
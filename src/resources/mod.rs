@@ -1,4 +1,14 @@
 pub mod level;
 pub mod game_config;
 pub mod game_stats;
-pub mod sound;
\ No newline at end of file
+pub mod sound;
+pub mod flow_field;
+pub mod enemy_archetypes;
+pub mod run_rng;
+pub mod shop_catalog;
+pub mod relics;
+pub mod loot;
+pub mod save_game;
+pub mod profile;
+pub mod pause;
+pub mod settings;
@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+/// Đang tạm dừng trong màn chơi. Thời gian ảo bị dừng cùng lúc,
+/// các system của màn chơi cũng bỏ qua input qua `game_running`.
+#[derive(Resource, Default)]
+pub struct PauseState {
+    pub paused: bool,
+}
+
+pub fn game_running(pause: Res<PauseState>) -> bool {
+    !pause.paused
+}
@@ -0,0 +1,285 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use crate::components::stats::{PlayerStats, StatKind, StatModifier, Stats};
+use crate::components::status::{StatusEffect, StatusKind};
+use crate::utils::storage::{data_file, write_atomic};
+
+const PROFILE_FILE_NAME: &str = "profile.ron";
+pub const PROFILE_VERSION: u32 = 1;
+
+// Shard nhận cuối run: mỗi ngần này kill được 1 shard, mỗi level qua được vài shard
+const KILLS_PER_SHARD: u32 = 10;
+const SHARDS_PER_LEVEL: u32 = 5;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum StartingWeapon {
+    #[default]
+    Standard,
+    EmberPistol,
+    TwinBarrel,
+    HeavySlug,
+}
+
+impl StartingWeapon {
+    pub const ALL: [StartingWeapon; 4] = [
+        StartingWeapon::Standard,
+        StartingWeapon::EmberPistol,
+        StartingWeapon::TwinBarrel,
+        StartingWeapon::HeavySlug,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StartingWeapon::Standard => "Standard Issue",
+            StartingWeapon::EmberPistol => "Ember Pistol",
+            StartingWeapon::TwinBarrel => "Twin Barrel",
+            StartingWeapon::HeavySlug => "Heavy Slug",
+        }
+    }
+
+    /// Gắn trang bị khởi đầu vào chỉ số vừa reset của player.
+    pub fn apply(self, stats: &mut PlayerStats, attributes: &mut Stats) {
+        match self {
+            StartingWeapon::Standard => {}
+            StartingWeapon::EmberPistol => {
+                stats.add_bullet_effect(StatusEffect::new(StatusKind::Burn, 3.0, 0.5));
+            }
+            StartingWeapon::TwinBarrel => {
+                attributes.add_modifier(StatModifier::add(StatKind::MaxBullets, 1.0));
+                attributes.add_modifier(StatModifier::multiply(StatKind::BulletSpeed, 0.85));
+            }
+            StartingWeapon::HeavySlug => {
+                attributes.add_modifier(StatModifier::add(StatKind::Damage, 1.0));
+                attributes.add_modifier(StatModifier::multiply(StatKind::BulletSpeed, 0.8));
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Difficulty {
+    #[default]
+    Normal,
+    Hard,
+    Nightmare,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Normal, Difficulty::Hard, Difficulty::Nightmare];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Nightmare => "Nightmare",
+        }
+    }
+
+    // Nhân thêm vào difficulty_multiplier của từng level
+    pub fn enemy_multiplier(self) -> f32 {
+        match self {
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.3,
+            Difficulty::Nightmare => 1.7,
+        }
+    }
+
+    pub fn reward_multiplier(self) -> f32 {
+        match self {
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+            Difficulty::Nightmare => 2.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnlockKind {
+    Weapon(StartingWeapon),
+    // id món trong danh mục shop có `locked: true`
+    ShopItem(&'static str),
+    // id archetype trong roster quái
+    Codex(&'static str),
+    Difficulty(Difficulty),
+}
+
+pub struct Unlock {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub cost: u32,
+    pub kind: UnlockKind,
+}
+
+/// Mọi thứ mua được bằng shard. Vũ khí Standard và độ khó Normal luôn có sẵn.
+pub const UNLOCKS: &[Unlock] = &[
+    Unlock { id: "weapon_ember_pistol", name: "Ember Pistol", cost: 15, kind: UnlockKind::Weapon(StartingWeapon::EmberPistol) },
+    Unlock { id: "weapon_heavy_slug", name: "Heavy Slug", cost: 25, kind: UnlockKind::Weapon(StartingWeapon::HeavySlug) },
+    Unlock { id: "weapon_twin_barrel", name: "Twin Barrel", cost: 30, kind: UnlockKind::Weapon(StartingWeapon::TwinBarrel) },
+    Unlock { id: "shock_rounds", name: "Shop: Shock Rounds", cost: 10, kind: UnlockKind::ShopItem("shock_rounds") },
+    Unlock { id: "bulk_ammo", name: "Shop: Bulk Ammo", cost: 20, kind: UnlockKind::ShopItem("bulk_ammo") },
+    Unlock { id: "twin_shot", name: "Shop: Twin Shot", cost: 25, kind: UnlockKind::ShopItem("twin_shot") },
+    Unlock { id: "codex_normal", name: "Codex: Golem", cost: 3, kind: UnlockKind::Codex("normal") },
+    Unlock { id: "codex_speed", name: "Codex: Runner", cost: 3, kind: UnlockKind::Codex("speed") },
+    Unlock { id: "codex_tank", name: "Codex: Tank", cost: 3, kind: UnlockKind::Codex("tank") },
+    Unlock { id: "codex_ranged", name: "Codex: Slinger", cost: 3, kind: UnlockKind::Codex("ranged") },
+    Unlock { id: "codex_shielded", name: "Codex: Bulwark", cost: 3, kind: UnlockKind::Codex("shielded") },
+    Unlock { id: "codex_splitter", name: "Codex: Splitter", cost: 3, kind: UnlockKind::Codex("splitter") },
+    Unlock { id: "codex_boss", name: "Codex: Golem King", cost: 8, kind: UnlockKind::Codex("boss") },
+    Unlock { id: "difficulty_hard", name: "Difficulty: Hard", cost: 20, kind: UnlockKind::Difficulty(Difficulty::Hard) },
+    Unlock { id: "difficulty_nightmare", name: "Difficulty: Nightmare", cost: 50, kind: UnlockKind::Difficulty(Difficulty::Nightmare) },
+];
+
+/// Hồ sơ người chơi giữ lại giữa các run. Field thiếu trong file cũ lấy
+/// giá trị mặc định, thay đổi lớn hơn thì xử lý trong `migrate`.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Profile {
+    // File thiếu version coi là v0
+    #[serde(default)]
+    pub version: u32,
+    pub shards: u32,
+    pub total_kills: u32,
+    pub levels_cleared: u32,
+    pub runs: u32,
+    pub unlocked: Vec<String>,
+    pub weapon: StartingWeapon,
+    pub difficulty: Difficulty,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            version: PROFILE_VERSION,
+            shards: 0,
+            total_kills: 0,
+            levels_cleared: 0,
+            runs: 0,
+            unlocked: Vec::new(),
+            weapon: StartingWeapon::Standard,
+            difficulty: Difficulty::Normal,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ProfileError {
+    Io(io::Error),
+    Corrupt(ron::error::SpannedError),
+    Serialize(ron::Error),
+    // File do bản game mới hơn ghi
+    Newer(u32),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::Io(err) => write!(f, "could not access profile: {err}"),
+            ProfileError::Corrupt(err) => write!(f, "profile is corrupt: {err}"),
+            ProfileError::Serialize(err) => write!(f, "could not encode profile: {err}"),
+            ProfileError::Newer(version) => write!(f, "profile version {version} is newer than supported ({PROFILE_VERSION})"),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+impl From<io::Error> for ProfileError {
+    fn from(err: io::Error) -> Self {
+        ProfileError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for ProfileError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        ProfileError::Corrupt(err)
+    }
+}
+
+impl From<ron::Error> for ProfileError {
+    fn from(err: ron::Error) -> Self {
+        ProfileError::Serialize(err)
+    }
+}
+
+impl Profile {
+    /// Đọc hồ sơ trên đĩa, lỗi thì bắt đầu hồ sơ mới. File hỏng được đổi
+    /// tên sang `.bak` thay vì xoá để còn cứu tay được.
+    pub fn load() -> Self {
+        let Some(path) = data_file(PROFILE_FILE_NAME) else { return Self::default() };
+        let result = fs::read_to_string(&path)
+            .map_err(ProfileError::from)
+            .and_then(|contents| Ok(ron::from_str::<Profile>(&contents)?))
+            .and_then(migrate);
+
+        match result {
+            Ok(profile) => profile,
+            Err(ProfileError::Io(err)) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(err) => {
+                warn!("Starting a fresh profile: {err}");
+                if let Err(err) = fs::rename(&path, path.with_extension("ron.bak")) {
+                    warn!("Could not back up old profile: {err}");
+                }
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = data_file(PROFILE_FILE_NAME) else { return };
+        let result = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(ProfileError::from)
+            .and_then(|contents| Ok(write_atomic(&path, &contents)?));
+        if let Err(err) = result {
+            warn!("Could not save profile: {err}");
+        }
+    }
+
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked == id)
+    }
+
+    pub fn has_weapon(&self, weapon: StartingWeapon) -> bool {
+        weapon == StartingWeapon::Standard || UNLOCKS.iter().any(|u| u.kind == UnlockKind::Weapon(weapon) && self.is_unlocked(u.id))
+    }
+
+    pub fn has_difficulty(&self, difficulty: Difficulty) -> bool {
+        difficulty == Difficulty::Normal || UNLOCKS.iter().any(|u| u.kind == UnlockKind::Difficulty(difficulty) && self.is_unlocked(u.id))
+    }
+
+    /// Trừ shard và mở khoá. Trả về false nếu đã có hoặc không đủ shard.
+    pub fn buy(&mut self, unlock: &Unlock) -> bool {
+        if self.is_unlocked(unlock.id) || self.shards < unlock.cost {
+            return false;
+        }
+        self.shards -= unlock.cost;
+        self.unlocked.push(unlock.id.to_string());
+        true
+    }
+
+    /// Cộng dồn kết quả một run và trả về số shard nhận được.
+    pub fn record_run(&mut self, kills: u32, levels: u32, difficulty: Difficulty) -> u32 {
+        let base = kills / KILLS_PER_SHARD + levels * SHARDS_PER_LEVEL;
+        let earned = (base as f32 * difficulty.reward_multiplier()).round() as u32;
+
+        self.runs += 1;
+        self.total_kills += kills;
+        self.levels_cleared += levels;
+        self.shards += earned;
+        earned
+    }
+}
+
+// Nâng hồ sơ cũ lên version hiện tại
+fn migrate(mut profile: Profile) -> Result<Profile, ProfileError> {
+    if profile.version > PROFILE_VERSION {
+        return Err(ProfileError::Newer(profile.version));
+    }
+    // v0: file chưa có trường version, các field thiếu đã được serde bù mặc định
+    if profile.version == 0 {
+        profile.version = 1;
+    }
+    Ok(profile)
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RelicKind {
+    // Cứ 3 lần nảy thì bắn ra một mảnh đạn nhỏ
+    ShardPrism,
+    // Giết đủ combo thì hồi 0.5 HP
+    BloodCombo,
+    // Nhặt coin gây nổ sát thương quái xung quanh
+    VolatileCoins,
+    // Trúng đòn thì đẩy lùi quái xung quanh
+    SpitePlate,
+}
+
+impl RelicKind {
+    pub const ALL: [RelicKind; 4] = [
+        RelicKind::ShardPrism,
+        RelicKind::BloodCombo,
+        RelicKind::VolatileCoins,
+        RelicKind::SpitePlate,
+    ];
+
+    // Trùng với id trong danh mục shop để stock tính cả relic rơi từ boss
+    pub fn id(self) -> &'static str {
+        match self {
+            RelicKind::ShardPrism => "shard_prism",
+            RelicKind::BloodCombo => "blood_combo",
+            RelicKind::VolatileCoins => "volatile_coins",
+            RelicKind::SpitePlate => "spite_plate",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RelicKind::ShardPrism => "Shard Prism",
+            RelicKind::BloodCombo => "Blood Combo",
+            RelicKind::VolatileCoins => "Volatile Coins",
+            RelicKind::SpitePlate => "Spite Plate",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            RelicKind::ShardPrism => Color::srgb(0.6, 0.9, 1.0),
+            RelicKind::BloodCombo => Color::srgb(1.0, 0.3, 0.3),
+            RelicKind::VolatileCoins => Color::srgb(1.0, 0.7, 0.2),
+            RelicKind::SpitePlate => Color::srgb(0.7, 0.7, 0.7),
+        }
+    }
+}
+
+/// Relic đã nhặt trong run hiện tại.
+#[derive(Resource, Default)]
+pub struct RelicInventory {
+    pub relics: Vec<RelicKind>,
+}
+
+impl RelicInventory {
+    pub fn has(&self, kind: RelicKind) -> bool {
+        self.relics.contains(&kind)
+    }
+
+    pub fn add(&mut self, kind: RelicKind) {
+        if !self.has(kind) {
+            self.relics.push(kind);
+        }
+    }
+
+    pub fn names(&self) -> String {
+        if self.relics.is_empty() {
+            return "-".to_string();
+        }
+        self.relics.iter().map(|relic| relic.name()).collect::<Vec<_>>().join(", ")
+    }
+}
+
+// Chuỗi giết liên tiếp, đứt nếu quá lâu không giết thêm
+#[derive(Resource)]
+pub struct KillCombo {
+    pub count: u32,
+    pub timer: Timer,
+}
+
+impl Default for KillCombo {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            timer: Timer::from_seconds(2.0, TimerMode::Once),
+        }
+    }
+}
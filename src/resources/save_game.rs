@@ -0,0 +1,248 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use crate::components::stats::{StatKind, Stats};
+use crate::components::status::StatusEffect;
+use crate::resources::profile::Difficulty;
+use crate::resources::relics::RelicKind;
+use crate::utils::storage::{data_file, write_atomic};
+
+const SAVE_FILE_NAME: &str = "run.save.ron";
+// Tăng khi đổi cấu trúc SavedRun, save cũ hơn được nâng lên trong `migrate`
+pub const SAVE_VERSION: u32 = 3;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct SavedModifier {
+    pub stat: StatKind,
+    pub add: f32,
+    pub multiply: f32,
+}
+
+/// Trạng thái run lưu lúc vào shop, đủ để chơi tiếp từ màn shop đó.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SavedRun {
+    pub version: u32,
+    // Level vừa qua, vào lại shop rồi mới sang level kế
+    pub level: usize,
+    // v1 chưa có, run cũ coi như chơi Normal
+    #[serde(default)]
+    pub difficulty: Difficulty,
+
+    pub money: u32,
+    pub spare_bullets: u32,
+    pub bullet_effects: Vec<StatusEffect>,
+    pub health: f32,
+    // Mọi nâng cấp vĩnh viễn, kể cả aura, dash và giá đạn
+    pub modifiers: Vec<SavedModifier>,
+
+    pub relics: Vec<RelicKind>,
+    pub purchases: HashMap<String, u32>,
+
+    pub total_time: f32,
+    pub shots_fired: u32,
+    pub parries: u32,
+    #[serde(default)]
+    pub kills: u32,
+
+    pub rng_state: u64,
+}
+
+// Chỉ đọc version trước để phân biệt save cũ với save hỏng
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+// Save v1/v2 lưu giá trị tuyệt đối của các chỉ số này thay vì modifier
+#[derive(Deserialize)]
+struct LegacyUpgrades {
+    bullet_cost: u32,
+    dash_charges: u32,
+    dash_distance: f32,
+    aura_max: f32,
+    aura_regen: f32,
+    aura_radius: f32,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    NoDataDir,
+    Io(io::Error),
+    Corrupt(ron::error::SpannedError),
+    Serialize(ron::Error),
+    // File do bản game mới hơn ghi
+    Version(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::NoDataDir => write!(f, "could not locate the user data directory"),
+            SaveError::Io(err) => write!(f, "could not access save file: {err}"),
+            SaveError::Corrupt(err) => write!(f, "save file is corrupt: {err}"),
+            SaveError::Serialize(err) => write!(f, "could not encode save: {err}"),
+            SaveError::Version(version) => write!(f, "save version {version} is newer than supported ({SAVE_VERSION})"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        SaveError::Corrupt(err)
+    }
+}
+
+impl From<ron::Error> for SaveError {
+    fn from(err: ron::Error) -> Self {
+        SaveError::Serialize(err)
+    }
+}
+
+/// Đọc save trên đĩa. `Ok(None)` khi chưa có save nào.
+pub fn read_save() -> Result<Option<SavedRun>, SaveError> {
+    let path = data_file(SAVE_FILE_NAME).ok_or(SaveError::NoDataDir)?;
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    parse_save(&contents).map(Some)
+}
+
+fn parse_save(contents: &str) -> Result<SavedRun, SaveError> {
+    let header: SaveHeader = ron::from_str(contents)?;
+    if header.version > SAVE_VERSION {
+        return Err(SaveError::Version(header.version));
+    }
+    let run = ron::from_str(contents)?;
+    migrate(run, header.version, contents)
+}
+
+// Nâng save cũ lên version hiện tại
+fn migrate(mut run: SavedRun, version: u32, contents: &str) -> Result<SavedRun, SaveError> {
+    // v1 -> v2: thêm difficulty và kills, serde đã bù mặc định
+    // v2 -> v3: aura, dash và giá đạn chuyển thành modifier, lưu phần chênh so với chỉ số gốc
+    if version < 3 {
+        let legacy: LegacyUpgrades = ron::from_str(contents)?;
+        let base = Stats::player();
+        let values = [
+            (StatKind::BulletCost, legacy.bullet_cost as f32),
+            (StatKind::DashCharges, legacy.dash_charges as f32),
+            (StatKind::DashDistance, legacy.dash_distance),
+            (StatKind::AuraCapacity, legacy.aura_max),
+            (StatKind::AuraRegen, legacy.aura_regen),
+            (StatKind::AuraRadius, legacy.aura_radius),
+        ];
+        for (stat, value) in values {
+            let add = value - base.get(stat);
+            if add != 0.0 {
+                run.modifiers.push(SavedModifier { stat, add, multiply: 1.0 });
+            }
+        }
+    }
+    run.version = SAVE_VERSION;
+    Ok(run)
+}
+
+pub fn write_save(run: &SavedRun) -> Result<(), SaveError> {
+    let path = data_file(SAVE_FILE_NAME).ok_or(SaveError::NoDataDir)?;
+    let contents = ron::ser::to_string_pretty(run, ron::ser::PrettyConfig::default())?;
+    write_atomic(&path, &contents)?;
+    Ok(())
+}
+
+/// Đổi tên save không đọc được sang `.bak` thay vì xoá, giống hồ sơ.
+pub fn backup_save() -> Result<(), SaveError> {
+    let Some(path) = data_file(SAVE_FILE_NAME) else { return Ok(()) };
+    fs::rename(&path, path.with_extension("ron.bak"))?;
+    Ok(())
+}
+
+pub fn delete_save() -> Result<(), SaveError> {
+    let Some(path) = data_file(SAVE_FILE_NAME) else { return Ok(()) };
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Save đọc được khi mở menu. `resume` bật khi người chơi bấm Continue,
+/// áp dụng ngay sau `reset_game_state` lúc rời menu. `notice` là thông báo
+/// hiện ở menu khi save có mà không chơi tiếp được.
+#[derive(Resource, Default)]
+pub struct SaveSlot {
+    pub run: Option<SavedRun>,
+    pub resume: bool,
+    pub notice: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::stats::StatModifier;
+
+    fn run() -> SavedRun {
+        SavedRun {
+            version: SAVE_VERSION,
+            level: 4,
+            difficulty: Difficulty::Hard,
+            money: 12,
+            spare_bullets: 3,
+            bullet_effects: Vec::new(),
+            health: 4.0,
+            modifiers: vec![SavedModifier { stat: StatKind::Damage, add: 1.0, multiply: 1.0 }],
+            relics: Vec::new(),
+            purchases: HashMap::new(),
+            total_time: 90.0,
+            shots_fired: 40,
+            parries: 2,
+            kills: 17,
+            rng_state: 7,
+        }
+    }
+
+    // Save v2 còn giữ giá trị tuyệt đối, nâng lên phải ra đúng các chỉ số đó
+    #[test]
+    fn v2_save_migrates_upgrades_into_modifiers() {
+        let contents = ron::to_string(&run()).unwrap().replacen(
+            "version:3",
+            "version:2,bullet_cost:2,dash_charges:2,dash_distance:160.0,aura_max:130.0,aura_regen:15.0,aura_radius:75.0",
+            1,
+        );
+        let migrated = parse_save(&contents).unwrap();
+        assert_eq!(migrated.version, SAVE_VERSION);
+        assert_eq!(migrated.level, 4);
+
+        let mut stats = Stats::player();
+        for m in &migrated.modifiers {
+            stats.add_modifier(StatModifier { stat: m.stat, add: m.add, multiply: m.multiply, timer: None, source: None });
+        }
+        assert_eq!(stats.get(StatKind::Damage), 2.0);
+        assert_eq!(stats.get(StatKind::BulletCost), 2.0);
+        assert_eq!(stats.get(StatKind::DashCharges), 2.0);
+        assert_eq!(stats.get(StatKind::DashDistance), 160.0);
+        assert_eq!(stats.get(StatKind::AuraCapacity), 130.0);
+        assert_eq!(stats.get(StatKind::AuraRegen), 15.0);
+        assert_eq!(stats.get(StatKind::AuraRadius), 75.0);
+        // Chỉ số không đổi thì không sinh modifier thừa
+        assert_eq!(migrated.modifiers.len(), 5);
+    }
+
+    #[test]
+    fn newer_save_is_rejected() {
+        let contents = ron::to_string(&run()).unwrap().replacen("version:3", "version:4", 1);
+        assert!(matches!(parse_save(&contents), Err(SaveError::Version(4))));
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+use bevy::window::{PresentMode, WindowMode, WindowResolution};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use crate::utils::storage::{data_file, write_atomic};
+
+const SETTINGS_FILE_NAME: &str = "settings.ron";
+
+// Kích thước logic của game, mọi độ phân giải đều scale về khung này
+pub const VIEW_WIDTH: f32 = 1280.0;
+pub const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+
+// Âm lượng gốc của nhạc nền trước khi nhân với cài đặt
+pub const BGM_BASE_VOLUME: f32 = 0.25;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "WINDOWED",
+            DisplayMode::Borderless => "BORDERLESS",
+            DisplayMode::Fullscreen => "FULLSCREEN",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            DisplayMode::Windowed => DisplayMode::Borderless,
+            DisplayMode::Borderless => DisplayMode::Fullscreen,
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
+        }
+    }
+
+    pub fn window_mode(self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+/// Cài đặt người chơi, đọc từ file trước khi tạo cửa sổ. Field thiếu lấy mặc định.
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub display_mode: DisplayMode,
+    pub resolution: (u32, u32),
+    // Hệ số nhân độ rung màn hình, 0 = tắt
+    pub screen_shake: f32,
+    pub vsync: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            display_mode: DisplayMode::Windowed,
+            resolution: RESOLUTIONS[0],
+            screen_shake: 1.0,
+            vsync: true,
+        }
+    }
+}
+
+impl Settings {
+    /// Đọc file cài đặt, thiếu hoặc lỗi thì dùng mặc định.
+    pub fn load() -> Self {
+        let Some(path) = data_file(SETTINGS_FILE_NAME) else { return Self::default() };
+        let settings = match fs::read_to_string(&path) {
+            Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+                warn!("Invalid settings file, using defaults: {err}");
+                Self::default()
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(err) => {
+                warn!("Could not read settings, using defaults: {err}");
+                Self::default()
+            }
+        };
+        settings.sanitized()
+    }
+
+    pub fn save(&self) {
+        let Some(path) = data_file(SETTINGS_FILE_NAME) else { return };
+        let result = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| write_atomic(&path, &contents).map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            warn!("Could not save settings: {err}");
+        }
+    }
+
+    // Chặn giá trị sửa tay trong file nằm ngoài khoảng hợp lệ
+    fn sanitized(mut self) -> Self {
+        self.master_volume = self.master_volume.clamp(0.0, 1.0);
+        self.music_volume = self.music_volume.clamp(0.0, 1.0);
+        self.sfx_volume = self.sfx_volume.clamp(0.0, 1.0);
+        self.screen_shake = self.screen_shake.clamp(0.0, 2.0);
+        if !RESOLUTIONS.contains(&self.resolution) {
+            self.resolution = RESOLUTIONS[0];
+        }
+        self
+    }
+
+    pub fn music_level(&self) -> f32 {
+        BGM_BASE_VOLUME * self.music_volume
+    }
+
+    pub fn window_resolution(&self) -> WindowResolution {
+        let (width, height) = self.resolution;
+        WindowResolution::new(width as f32, height as f32).with_scale_factor_override(width as f32 / VIEW_WIDTH)
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync { PresentMode::AutoVsync } else { PresentMode::AutoNoVsync }
+    }
+}
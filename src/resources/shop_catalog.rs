@@ -0,0 +1,270 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use crate::components::status::{StatusEffect, StatusKind};
+use crate::resources::profile::Profile;
+use crate::resources::relics::RelicKind;
+use crate::utils::ron_loader::Validate;
+
+pub const SHOP_CATALOG_PATH: &str = "data/items.catalog.ron";
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rarity {
+    Common,
+    Rare,
+    Epic,
+}
+
+impl Rarity {
+    // Trọng số khi bốc ngẫu nhiên món hàng
+    pub fn weight(self) -> u32 {
+        match self {
+            Rarity::Common => 60,
+            Rarity::Rare => 30,
+            Rarity::Epic => 10,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Rarity::Common => "COMMON",
+            Rarity::Rare => "RARE",
+            Rarity::Epic => "EPIC",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Rarity::Common => Color::WHITE,
+            Rarity::Rare => Color::srgb(0.4, 0.7, 1.0),
+            Rarity::Epic => Color::srgb(0.8, 0.4, 1.0),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BuffType {
+    Heal,
+    DamageUp,
+    EmberRounds,
+    FrostRounds,
+    HeavyRounds,
+    ShockRounds,
+    DashCharge,
+    DashDistance,
+    AuraCapacity,
+    AuraRegen,
+    AuraRadius,
+    MaxHp,
+    MoveSpeed,
+    BulletSpeed,
+    BulletLifetime,
+    KickPower,
+    MagnetRange,
+    BulletDiscount,
+    ExtraBullet,
+    // Relic chỉ bán một lần, id trong danh mục phải trùng RelicKind::id
+    Relic(RelicKind),
+}
+
+impl BuffType {
+    // Hiệu ứng gắn thêm vào đạn khi mua
+    pub fn bullet_effect(self) -> Option<StatusEffect> {
+        match self {
+            BuffType::EmberRounds => Some(StatusEffect::new(StatusKind::Burn, 3.0, 0.5)),
+            BuffType::FrostRounds => Some(StatusEffect::new(StatusKind::Freeze, 2.0, 0.3)),
+            BuffType::HeavyRounds => Some(StatusEffect::new(StatusKind::Knockback, 0.25, 400.0)),
+            BuffType::ShockRounds => Some(StatusEffect::new(StatusKind::Stun, 0.6, 0.0)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct CatalogItem {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    pub rarity: Rarity,
+    pub base_cost: u32,
+    // Giá tăng thêm sau mỗi level đã qua
+    pub cost_per_level: u32,
+    // Số lần mua tối đa trong một run, không ghi là không giới hạn
+    #[serde(default)]
+    pub stock: Option<u32>,
+    // Phải mua các món này trước thì món này mới xuất hiện
+    #[serde(default)]
+    pub requires: Vec<String>,
+    // Phải mở khoá trong hồ sơ (cùng id) mới được bán
+    #[serde(default)]
+    pub locked: bool,
+    pub buff: BuffType,
+}
+
+impl CatalogItem {
+    pub fn cost(&self, level: usize) -> u32 {
+        self.base_cost + self.cost_per_level * level.saturating_sub(1) as u32
+    }
+}
+
+/// Danh mục hàng của shop, đọc từ file RON trong `assets/data`.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct ShopCatalog {
+    pub items: Vec<CatalogItem>,
+}
+
+impl ShopCatalog {
+    /// Bốc `count` món khác nhau theo trọng số độ hiếm, bỏ qua món đã hết
+    /// hàng, chưa đủ điều kiện hoặc chưa mở khoá.
+    pub fn roll_offers(&self, history: &PurchaseHistory, profile: &Profile, rng: &mut impl Rng, count: usize) -> Vec<&CatalogItem> {
+        let mut pool: Vec<&CatalogItem> = self
+            .items
+            .iter()
+            .filter(|item| history.is_available(item))
+            .filter(|item| !item.locked || profile.is_unlocked(&item.id))
+            .collect();
+
+        let mut offers = Vec::new();
+        while offers.len() < count && !pool.is_empty() {
+            let total: u32 = pool.iter().map(|item| item.rarity.weight()).sum();
+            let mut roll = rng.gen_range(0..total);
+            let index = pool
+                .iter()
+                .position(|item| {
+                    let weight = item.rarity.weight();
+                    if roll < weight {
+                        true
+                    } else {
+                        roll -= weight;
+                        false
+                    }
+                })
+                .unwrap_or(0);
+            offers.push(pool.swap_remove(index));
+        }
+        offers
+    }
+}
+
+impl Validate for ShopCatalog {
+    fn validate(&mut self) {
+        // Mua và lưu save theo id nên id trùng thì chỉ giữ món đầu tiên
+        let mut seen = HashSet::new();
+        self.items.retain(|item| {
+            let is_new = seen.insert(item.id.clone());
+            if !is_new {
+                error!("Shop item '{}' is listed more than once, dropping the duplicate", item.id);
+            }
+            is_new
+        });
+
+        // Hồ sơ và save lưu relic theo RelicKind::id, lệch id thì mua xong không ghi nhận được
+        self.items.retain(|item| match item.buff {
+            BuffType::Relic(kind) if item.id != kind.id() => {
+                error!("Shop item '{}' sells relic '{}' under a different id, dropping it", item.id, kind.id());
+                false
+            }
+            _ => true,
+        });
+
+        // `requires` trỏ vào id không có thì món đó không bao giờ xuất hiện.
+        // Bỏ một món có thể làm món khác mồ côi theo, nên lặp tới khi ổn định
+        loop {
+            let ids: HashSet<String> = self.items.iter().map(|item| item.id.clone()).collect();
+            let before = self.items.len();
+            self.items.retain(|item| match item.requires.iter().find(|id| !ids.contains(*id)) {
+                Some(missing) => {
+                    error!("Shop item '{}' requires unknown item '{missing}', dropping it", item.id);
+                    false
+                }
+                None => true,
+            });
+            if self.items.len() == before {
+                break;
+            }
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ShopCatalogHandle {
+    pub catalog: Handle<ShopCatalog>,
+}
+
+/// Số lần đã mua từng món trong run hiện tại, dùng cho stock và điều kiện.
+#[derive(Resource, Default)]
+pub struct PurchaseHistory {
+    pub counts: HashMap<String, u32>,
+}
+
+impl PurchaseHistory {
+    pub fn count(&self, id: &str) -> u32 {
+        self.counts.get(id).copied().unwrap_or(0)
+    }
+
+    pub fn record(&mut self, id: &str) {
+        *self.counts.entry(id.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn is_available(&self, item: &CatalogItem) -> bool {
+        let in_stock = match item.stock {
+            Some(stock) => self.count(&item.id) < stock,
+            None => true,
+        };
+        in_stock && item.requires.iter().all(|id| self.count(id) > 0)
+    }
+}
+
+pub fn setup_shop_catalog(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(ShopCatalogHandle {
+        catalog: asset_server.load(SHOP_CATALOG_PATH),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, requires: &[&str], buff: &str) -> String {
+        format!(r#"(
+            id: "{id}", name: "", description: "", icon: "",
+            rarity: Common, base_cost: 1, cost_per_level: 0,
+            requires: {requires:?}, buff: {buff},
+        )"#)
+    }
+
+    fn ids(catalog: &ShopCatalog) -> Vec<&str> {
+        catalog.items.iter().map(|item| item.id.as_str()).collect()
+    }
+
+    // Id trùng, relic lệch id và điều kiện trỏ vào món không tồn tại đều bị bỏ khi load
+    #[test]
+    fn validate_drops_broken_entries() {
+        let items = [
+            item("potion", &[], "Heal"),
+            item("potion", &[], "DamageUp"),
+            item("prism", &[], "Relic(ShardPrism)"),
+            item("orphan", &["prism"], "MaxHp"),
+            item("typo", &["potoin"], "MaxHp"),
+            item("boots", &["potion"], "DashDistance"),
+        ];
+        let mut catalog: ShopCatalog = ron::from_str(&format!("(items: [{}])", items.join(","))).unwrap();
+        catalog.validate();
+
+        assert_eq!(ids(&catalog), ["potion", "boots"]);
+        assert_eq!(catalog.items[0].buff, BuffType::Heal);
+    }
+
+    #[test]
+    fn shipped_catalog_is_valid() {
+        let mut catalog: ShopCatalog = ron::from_str(include_str!("../../assets/data/items.catalog.ron")).unwrap();
+        let before = catalog.items.len();
+        catalog.validate();
+        assert_eq!(catalog.items.len(), before);
+    }
+}
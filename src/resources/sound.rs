@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 use bevy::asset::LoadState;
+use crate::resources::settings::Settings;
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct SoundAssets {
     pub select: Handle<AudioSource>,
     pub coin: Handle<AudioSource>,
@@ -45,6 +46,7 @@ pub fn start_background_music_when_ready(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     sound_assets: Res<SoundAssets>,
+    settings: Res<Settings>,
     music_query: Query<Entity, With<BackgroundMusic>>,
 ) {
     if !music_query.is_empty() {
@@ -62,7 +64,7 @@ pub fn start_background_music_when_ready(
         AudioBundle {
             source: sound_assets.bgm.clone(),
             settings: PlaybackSettings::LOOP
-                .with_volume(bevy::audio::Volume::new(0.25)),
+                .with_volume(bevy::audio::Volume::new(settings.music_level())),
         },
         BackgroundMusic,
     ));
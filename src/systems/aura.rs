@@ -1,10 +1,16 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
-use crate::components::player::{Player, Aura, HasAura};
-use crate::components::bullet::Bullet;
+use rand::Rng;
+use crate::components::player::{Player, Aura, AuraEnergy, HasAura};
+use crate::components::bullet::{Bullet, Faction, Recalling};
+use crate::components::stats::{PlayerStats, StatKind, Stats};
+use crate::resources::game_config::ParrySettings;
+use crate::resources::game_stats::GameStats;
+use crate::resources::sound::SoundAssets;
+use crate::vfx::{HitStopEvent, ScreenFlashEvent};
 
-const AURA_RADIUS: f32 = 60.0;             
-const AURA_DIAMETER: f32 = AURA_RADIUS * 2.2; 
+// Kích thước sprite so với bán kính aura
+const AURA_DIAMETER_SCALE: f32 = 2.2; 
 
 const AURA_PATH: &str = "sprites/ui/aura.png";
 const AURA_SCALE: f32 = 0.5;
@@ -13,9 +19,20 @@ const ALPHA_NORMAL: f32 = 0.05;
 const ALPHA_ACTIVE: f32 = 0.2; 
 
 const AURA_COLOR: Color = Color::srgb(0.0, 1.0, 1.0);
+const AURA_DEPLETED_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+
+const SLOW_DRAIN_PER_SEC: f32 = 30.0;
+pub const KICK_COST: f32 = 20.0;
 
 const BULLET_SPEED_SLOW: f32 = 50.0;
-const BULLET_SPEED_KICK: f32 = 400.0; 
+
+pub const RECALL_COST: f32 = 30.0;
+const RECALL_SPEED: f32 = 450.0;
+// Độ cong tối đa lúc mới gọi, thẳng dần khi đạn tới gần
+const RECALL_CURVE: f32 = 0.8;
+
+const PARRY_SOUND_SPEED: f32 = 1.6;
+const PARRY_FLASH_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.35);
 
 const ROTATE_SPEED_NORMAL: f32 = 1.5; 
 const ROTATE_SPEED_ACTIVE: f32 = 10.0;
@@ -23,12 +40,13 @@ const ROTATE_SPEED_ACTIVE: f32 = 10.0;
 #[derive(Component)]
 pub struct AuraVisual;
 
+#[allow(clippy::type_complexity)]
 pub fn spawn_aura(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    player_query: Query<Entity, (With<Player>, Without<HasAura>)>,
+    player_query: Query<(Entity, &Stats), (With<Player>, Without<HasAura>)>,
 ) {
-    if let Ok(player_entity) = player_query.get_single() {
+    if let Ok((player_entity, stats)) = player_query.get_single() {
         let aura = commands.spawn((
             SpriteBundle {
                 texture: asset_server.load(AURA_PATH),
@@ -36,7 +54,7 @@ pub fn spawn_aura(
                     .with_scale(Vec3::splat(AURA_SCALE)),
                 sprite: Sprite {
                     color: AURA_COLOR.with_alpha(ALPHA_NORMAL),
-                    custom_size: Some(Vec2::splat(AURA_DIAMETER)),
+                    custom_size: Some(Vec2::splat(stats.get(StatKind::AuraRadius) * AURA_DIAMETER_SCALE)),
                     ..default()
                 },
                 ..default()
@@ -54,34 +72,62 @@ pub fn spawn_aura(
 pub fn aura_visual_system(
     time: Res<Time>, 
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    player_query: Query<(&AuraEnergy, &Stats), With<Player>>,
     mut aura_query: Query<(&mut Sprite, &mut Transform), With<AuraVisual>>,
 ) {
-    let is_active =
+    let Ok((energy, stats)) = player_query.get_single() else { return };
+    let is_depleted = !energy.can_spend(KICK_COST);
+    let is_active = !is_depleted && (
         keyboard_input.pressed(KeyCode::ShiftLeft) ||
-        keyboard_input.pressed(KeyCode::Space);
+        keyboard_input.pressed(KeyCode::Space));
 
     if let Ok((mut sprite, mut transform)) = aura_query.get_single_mut() {
         let alpha = if is_active { ALPHA_ACTIVE } else { ALPHA_NORMAL };
-        sprite.color.set_alpha(alpha);
+        let color = if is_depleted { AURA_DEPLETED_COLOR } else { AURA_COLOR };
+        sprite.color = color.with_alpha(alpha);
+        sprite.custom_size = Some(Vec2::splat(stats.get(StatKind::AuraRadius) * AURA_DIAMETER_SCALE));
 
         let rotate_speed = if is_active { ROTATE_SPEED_ACTIVE } else { ROTATE_SPEED_NORMAL };
         transform.rotate_z(rotate_speed * time.delta_seconds());
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn aura_logic_system(
+    mut commands: Commands,
+    time: Res<Time>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
+    parry: Res<ParrySettings>,
+    sound_assets: Res<SoundAssets>,
+    mut game_stats: ResMut<GameStats>,
+    mut ev_hit_stop: EventWriter<HitStopEvent>,
+    mut ev_flash: EventWriter<ScreenFlashEvent>,
+    // Lần bấm Space hụt gần nhất, chờ đạn bay vào để parry
+    mut parry_pressed_at: Local<Option<f32>>,
 
-    player_query: Query<&Transform, With<Player>>,
-    mut bullet_query: Query<(&Transform, &mut Bullet)>,
+    mut player_query: Query<(&Transform, &PlayerStats, &Stats, &mut AuraEnergy), With<Player>>,
+    mut bullet_query: Query<(&Transform, &mut Bullet, &mut Sprite)>,
 ) {
-    let Ok(player_transform) = player_query.get_single() else { return };
+    let Ok((player_transform, stats, attributes, mut energy)) = player_query.get_single_mut() else { return };
     let player_pos = player_transform.translation.truncate();
+    let now = time.elapsed_seconds();
+    let radius = attributes.get(StatKind::AuraRadius);
 
-    let is_slowing = keyboard_input.pressed(KeyCode::ShiftLeft);
-    let is_kicking = keyboard_input.just_pressed(KeyCode::Space);
+    let wants_slow = keyboard_input.pressed(KeyCode::ShiftLeft);
+    let is_slowing = wants_slow && energy.current > 0.0;
+    let is_kicking = keyboard_input.just_pressed(KeyCode::Space) && energy.can_spend(KICK_COST);
+
+    // Giữ Shift thì hao năng lượng, thả ra mới hồi lại
+    if wants_slow {
+        energy.current = (energy.current - SLOW_DRAIN_PER_SEC * time.delta_seconds()).max(0.0);
+    } else {
+        let regen = attributes.get(StatKind::AuraRegen) * time.delta_seconds();
+        energy.current = (energy.current + regen).min(attributes.get(StatKind::AuraCapacity));
+    }
+    let mut has_kicked = false;
+    let mut has_parried = false;
 
     let (camera, camera_transform) = camera_query.single();
     let window = window_query.single();
@@ -89,25 +135,253 @@ pub fn aura_logic_system(
         .cursor_position()
         .and_then(|p| camera.viewport_to_world_2d(camera_transform, p));
 
-    for (bullet_transform, mut bullet) in bullet_query.iter_mut() {
+    for (bullet_transform, mut bullet, mut sprite) in bullet_query.iter_mut() {
         let bullet_pos = bullet_transform.translation.truncate();
 
-        if player_pos.distance(bullet_pos) > AURA_RADIUS {
+        let is_inside = player_pos.distance(bullet_pos) <= radius;
+        // Đạn vừa bắn ra nằm sẵn trong aura, không tính là bay vào
+        let just_entered = is_inside && !bullet.in_aura && !bullet.is_added();
+        bullet.in_aura = is_inside;
+        if !is_inside {
+            continue;
+        }
+
+        let Some(target) = cursor_world else { continue };
+        let direction = (target - player_pos).normalize_or_zero();
+
+        // Bấm Space ngay trước khi đạn chạm mép aura: parry hoàn hảo
+        let is_perfect = just_entered
+            && !is_kicking
+            && parry_pressed_at.is_some_and(|pressed_at| parry.is_perfect(pressed_at, now));
+
+        if is_perfect {
+            kick_bullet(&mut bullet, &mut sprite, stats, attributes, direction, attributes.get(StatKind::KickSpeed) * parry.speed_multiplier);
+            bullet.parry_bonus = parry.damage_multiplier;
+            has_parried = true;
             continue;
         }
 
         if is_kicking {
-            if let Some(target) = cursor_world {
-                bullet.direction = (target - player_pos).normalize_or_zero();
-                bullet.speed = BULLET_SPEED_KICK;
-                bullet.lifetime.reset();
-                bullet.is_despawning = false;
-                
-                continue; 
-            }
+            kick_bullet(&mut bullet, &mut sprite, stats, attributes, direction, attributes.get(StatKind::KickSpeed));
+            has_kicked = true;
+            continue; 
         }
         if is_slowing {
             bullet.speed = BULLET_SPEED_SLOW;
         }
     }
-}
\ No newline at end of file
+
+    if has_parried {
+        *parry_pressed_at = None;
+        game_stats.parries += 1;
+        commands.spawn(AudioBundle {
+            source: sound_assets.bounce.clone(),
+            settings: PlaybackSettings::DESPAWN.with_speed(PARRY_SOUND_SPEED),
+        });
+        ev_hit_stop.send(HitStopEvent { duration: parry.hit_stop });
+        ev_flash.send(ScreenFlashEvent { color: PARRY_FLASH_COLOR, duration: parry.flash_duration });
+    }
+
+    // Một lần bấm Space tốn năng lượng một lần, dù đá trúng bao nhiêu viên hay hụt.
+    // Bấm hụt mới mở cửa sổ parry, nên spam Space cũng phải trả giá.
+    if is_kicking || has_parried {
+        energy.current = (energy.current - KICK_COST).max(0.0);
+    }
+    if has_kicked {
+        *parry_pressed_at = None;
+    } else if is_kicking {
+        *parry_pressed_at = Some(now);
+    }
+}
+
+fn kick_bullet(bullet: &mut Bullet, sprite: &mut Sprite, stats: &PlayerStats, attributes: &Stats, direction: Vec2, speed: f32) {
+    bullet.direction = direction;
+    bullet.speed = speed;
+    bullet.lifetime.reset();
+    bullet.is_despawning = false;
+    bullet.bounces = 0;
+    // Parry cộng thêm sát thương chỉ cho một lần đá, sát thương gốc của đạn giữ nguyên
+    bullet.parry_bonus = 1.0;
+
+    // Đá ngược đạn của quái: đổi phe để nó gây sát thương cho quái
+    if bullet.faction == Faction::Enemy {
+        bullet.faction = Faction::Player;
+        bullet.damage = attributes.get(StatKind::Damage);
+        bullet.effects = stats.bullet_effects.clone();
+        bullet.hit_entities.clear();
+        sprite.color = Color::WHITE;
+    }
+}
+
+/// Bấm R để gọi đạn của mình bay vòng về phía player, gây sát thương
+/// cho quái trên đường bay. Tới mép aura thì chậm lại để đá tiếp.
+/// Đụng tường thì `bullet_ricochet` huỷ gọi về, nên system này phải chạy trước nó.
+pub fn bullet_recall_system(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut player_query: Query<(Entity, &Transform, &Stats, &mut AuraEnergy), With<Player>>,
+    mut bullet_query: Query<(Entity, &Transform, &mut Bullet, Option<&Recalling>)>,
+) {
+    let Ok((player_entity, player_transform, stats, mut energy)) = player_query.get_single_mut() else { return };
+    let player_pos = player_transform.translation.truncate();
+    let radius = stats.get(StatKind::AuraRadius);
+    let mut rng = rand::thread_rng();
+
+    let wants_recall = keyboard_input.just_pressed(KeyCode::KeyR) && energy.can_spend(RECALL_COST);
+    let mut has_recalled = false;
+
+    for (entity, bullet_transform, mut bullet, recalling) in bullet_query.iter_mut() {
+        let bullet_pos = bullet_transform.translation.truncate();
+        let to_player = player_pos - bullet_pos;
+        let distance = to_player.length();
+
+        // Đạn đang chạy animation biến mất thì không gọi về nữa
+        if bullet.is_despawning {
+            if recalling.is_some() {
+                commands.entity(entity).remove::<Recalling>();
+            }
+            continue;
+        }
+
+        match recalling {
+            None => {
+                let is_own = bullet.owner == Some(player_entity) && bullet.faction == Faction::Player;
+                if !wants_recall || !is_own || distance <= radius {
+                    continue;
+                }
+                commands.entity(entity).insert(Recalling {
+                    side: if rng.gen_bool(0.5) { 1.0 } else { -1.0 },
+                    start_distance: distance,
+                });
+                bullet.lifetime.reset();
+                bullet.hit_entities.clear();
+                bullet.speed = RECALL_SPEED;
+                has_recalled = true;
+            }
+            Some(recall) => {
+                if distance <= radius {
+                    bullet.speed = BULLET_SPEED_SLOW;
+                    commands.entity(entity).remove::<Recalling>();
+                    continue;
+                }
+                let bend = (distance / recall.start_distance).clamp(0.0, 1.0) * RECALL_CURVE;
+                let forward = to_player / distance;
+                bullet.direction = (forward + forward.perp() * recall.side * bend).normalize_or(forward);
+                bullet.speed = RECALL_SPEED;
+            }
+        }
+    }
+
+    if has_recalled {
+        energy.current -= RECALL_COST;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::render::camera::{camera_system, ManualTextureViews};
+    use bevy::window::{WindowCreated, WindowResized, WindowResolution, WindowScaleFactorChanged};
+    use std::time::Duration;
+
+    const FRAME: Duration = Duration::from_millis(16);
+    const OUTSIDE: Vec3 = Vec3::new(200.0, 0.0, 0.0);
+    const INSIDE: Vec3 = Vec3::new(30.0, 0.0, 0.0);
+
+    fn setup() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .init_asset::<Image>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowCreated>()
+            .add_event::<WindowScaleFactorChanged>()
+            .add_event::<HitStopEvent>()
+            .add_event::<ScreenFlashEvent>()
+            .init_resource::<ManualTextureViews>()
+            .init_resource::<Time>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ParrySettings>()
+            .init_resource::<GameStats>()
+            .init_resource::<SoundAssets>()
+            .add_systems(Update, aura_logic_system);
+
+        let world = app.world_mut();
+        let mut window = Window { resolution: WindowResolution::new(800.0, 600.0), ..default() };
+        // Con trỏ nằm bên phải player để cú đá có hướng
+        window.set_cursor_position(Some(Vec2::new(500.0, 300.0)));
+        world.spawn((window, PrimaryWindow));
+        world.spawn(Camera2dBundle::default());
+        world.run_system_once(camera_system::<OrthographicProjection>);
+
+        let stats = Stats::player();
+        let player = world.spawn((Player, Transform::default(), PlayerStats::default(), AuraEnergy::full(&stats), stats)).id();
+        (app, player)
+    }
+
+    fn frame(app: &mut App, press_space: bool) {
+        let world = app.world_mut();
+        world.resource_mut::<Time>().advance_by(FRAME);
+        let mut input = world.resource_mut::<ButtonInput<KeyCode>>();
+        input.release_all();
+        input.clear();
+        if press_space {
+            input.press(KeyCode::Space);
+        }
+        app.update();
+    }
+
+    fn spawn_bullet(app: &mut App, owner: Option<Entity>, position: Vec3) -> Entity {
+        app.world_mut().spawn((Transform::from_translation(position), Bullet { owner, ..default() }, Sprite::default())).id()
+    }
+
+    fn parries(app: &App) -> u32 {
+        app.world().resource::<GameStats>().parries
+    }
+
+    #[test]
+    fn parry_when_press_lands_just_before_entry() {
+        let (mut app, _) = setup();
+        let bullet = spawn_bullet(&mut app, None, OUTSIDE);
+        frame(&mut app, false);
+
+        frame(&mut app, true);
+        app.world_mut().get_mut::<Transform>(bullet).unwrap().translation = INSIDE;
+        frame(&mut app, false);
+
+        assert_eq!(parries(&app), 1);
+    }
+
+    // Bấm hụt rồi bắn ngay: viên vừa bắn nằm sẵn trong aura, không được thành parry
+    #[test]
+    fn freshly_fired_bullet_is_not_a_parry() {
+        let (mut app, player) = setup();
+        frame(&mut app, true);
+        let bullet = spawn_bullet(&mut app, Some(player), Vec3::ZERO);
+        let speed = app.world().get::<Bullet>(bullet).unwrap().speed;
+        frame(&mut app, false);
+
+        assert_eq!(parries(&app), 0);
+        assert_eq!(app.world().get::<Bullet>(bullet).unwrap().speed, speed);
+    }
+
+    #[test]
+    fn missed_press_costs_energy_and_expires() {
+        let (mut app, player) = setup();
+        let bullet = spawn_bullet(&mut app, None, OUTSIDE);
+        frame(&mut app, false);
+
+        frame(&mut app, true);
+        let capacity = Stats::player().get(StatKind::AuraCapacity);
+        assert_eq!(app.world().get::<AuraEnergy>(player).unwrap().current, capacity - KICK_COST);
+
+        let window = app.world().resource::<ParrySettings>().window;
+        while app.world().resource::<Time>().elapsed_seconds() < 2.0 * window + 0.1 {
+            frame(&mut app, false);
+        }
+        app.world_mut().get_mut::<Transform>(bullet).unwrap().translation = INSIDE;
+        frame(&mut app, false);
+
+        assert_eq!(parries(&app), 0);
+    }
+}
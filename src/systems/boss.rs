@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+use crate::components::boss::{Boss, BossState, Shockwave, WeakPoint, WeakPointVisual};
+use crate::components::enemy::{EnemySpeed, Health};
+use crate::components::player::{Player, Invulnerable};
+use crate::events::PlayerDamaged;
+use crate::resources::flow_field::FlowField;
+use crate::resources::level::LevelManager;
+use crate::resources::sound::SoundAssets;
+use crate::systems::ricochet::PLAYER_IFRAME_TIME;
+use crate::systems::wave::EnemySpawner;
+use crate::vfx::ScreenShakeEvent;
+
+const CHASE_TIME: f32 = 2.5;
+const WINDUP_TIME: f32 = 0.8;
+const CHARGE_TIME: f32 = 0.7;
+const CHARGE_SPEED: f32 = 520.0;
+const WINDUP_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
+
+const SHOCKWAVE_PATH: &str = "sprites/ui/aura.png";
+const SHOCKWAVE_MAX_RADIUS: f32 = 320.0;
+const SHOCKWAVE_SPEED: f32 = 260.0;
+const SHOCKWAVE_THICKNESS: f32 = 20.0;
+const SHOCKWAVE_COLOR: Color = Color::srgba(1.0, 0.5, 0.1, 0.6);
+
+const SUMMON_RADIUS: f32 = 90.0;
+// Khoảng cách từ tâm boss tới điểm yếu, tính theo toạ độ local (boss đã scale)
+const WEAK_POINT_OFFSET: f32 = 10.0;
+
+#[allow(clippy::too_many_arguments)]
+pub fn boss_behaviour(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    spawner: EnemySpawner,
+    flow_field: Res<FlowField>,
+    level_manager: Res<LevelManager>,
+    mut ev_shake: EventWriter<ScreenShakeEvent>,
+    mut boss_query: Query<(&mut Transform, &mut Sprite, &mut Boss, &EnemySpeed, &Health)>,
+    player_query: Query<&Transform, (With<Player>, Without<Boss>)>,
+) {
+    let Ok(player_transform) = player_query.get_single() else { return };
+    let player_pos = player_transform.translation.truncate();
+
+    for (mut transform, mut sprite, mut boss, speed, health) in boss_query.iter_mut() {
+        boss.state_timer.tick(time.delta());
+
+        // Phase theo lượng máu còn lại, phase sau nhanh và hung hãn hơn
+        let hp_ratio = health.current / health.max;
+        boss.phase = if hp_ratio > 2.0 / 3.0 { 1 } else if hp_ratio > 1.0 / 3.0 { 2 } else { 3 };
+        let phase_speed = 1.0 + 0.25 * (boss.phase - 1) as f32;
+
+        let boss_pos = transform.translation.truncate();
+
+        match boss.state {
+            BossState::Chase => {
+                let target = flow_field.waypoint(boss_pos).unwrap_or(player_pos);
+                let direction = (target - boss_pos).normalize_or_zero();
+                transform.translation += (direction * speed.speed * phase_speed * time.delta_seconds()).extend(0.0);
+                if direction.x != 0.0 {
+                    sprite.flip_x = direction.x < 0.0;
+                }
+
+                if boss.state_timer.finished() {
+                    let next = next_attack(boss.phase, boss.attack_count);
+                    boss.attack_count += 1;
+                    enter_state(&mut boss, next);
+                }
+            }
+            BossState::ChargeWindup => {
+                sprite.color = WINDUP_COLOR;
+                boss.charge_direction = (player_pos - boss_pos).normalize_or_zero();
+                sprite.flip_x = boss.charge_direction.x < 0.0;
+
+                if boss.state_timer.finished() {
+                    sprite.color = boss.base_color;
+                    enter_state(&mut boss, BossState::Charging);
+                }
+            }
+            BossState::Charging => {
+                let velocity = boss.charge_direction * CHARGE_SPEED * phase_speed;
+                transform.translation += (velocity * time.delta_seconds()).extend(0.0);
+
+                if boss.state_timer.finished() {
+                    enter_state(&mut boss, BossState::Chase);
+                }
+            }
+            BossState::Shockwave => {
+                ev_shake.send(ScreenShakeEvent { intensity: 6.0, duration: 0.3 });
+                commands.spawn((
+                    SpriteBundle {
+                        texture: asset_server.load(SHOCKWAVE_PATH),
+                        transform: Transform::from_translation(boss_pos.extend(0.5)),
+                        sprite: Sprite {
+                            color: SHOCKWAVE_COLOR,
+                            custom_size: Some(Vec2::ZERO),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    Shockwave {
+                        radius: 0.0,
+                        max_radius: SHOCKWAVE_MAX_RADIUS,
+                        speed: SHOCKWAVE_SPEED * phase_speed,
+                        damage: 1.0,
+                        has_hit: false,
+                    },
+                ));
+                enter_state(&mut boss, BossState::Chase);
+            }
+            BossState::Summon => {
+                let adds = 1 + boss.phase as usize;
+                for i in 0..adds {
+                    let angle = TAU * i as f32 / adds as f32;
+                    spawner.spawn(
+                        &mut commands,
+                        &boss.summon,
+                        level_manager.difficulty_multiplier,
+                        boss_pos + Vec2::from_angle(angle) * SUMMON_RADIUS,
+                    );
+                }
+                enter_state(&mut boss, BossState::Chase);
+            }
+        }
+    }
+}
+
+/// Xoay điểm yếu ra lưng boss (ngược phía player) để chỉ đạn nảy mới với tới,
+/// và đặt sprite điểm yếu theo hướng đó.
+#[allow(clippy::type_complexity)]
+pub fn update_weak_points(
+    mut boss_query: Query<(&Transform, &mut WeakPoint)>,
+    player_query: Query<&Transform, (With<Player>, Without<WeakPoint>)>,
+    mut visual_query: Query<(&Parent, &mut Transform), (With<WeakPointVisual>, Without<WeakPoint>, Without<Player>)>,
+) {
+    let Ok(player_transform) = player_query.get_single() else { return };
+    let player_pos = player_transform.translation.truncate();
+
+    for (transform, mut weak_point) in boss_query.iter_mut() {
+        let away = transform.translation.truncate() - player_pos;
+        if away != Vec2::ZERO {
+            weak_point.facing = away.normalize();
+        }
+    }
+
+    for (parent, mut transform) in visual_query.iter_mut() {
+        let Ok((_, weak_point)) = boss_query.get(parent.get()) else { continue };
+
+        transform.translation = (weak_point.facing * WEAK_POINT_OFFSET).extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(weak_point.facing.to_angle());
+    }
+}
+
+fn next_attack(phase: u8, attack_count: u32) -> BossState {
+    let pattern: &[BossState] = match phase {
+        1 => &[BossState::ChargeWindup],
+        2 => &[BossState::ChargeWindup, BossState::Shockwave],
+        _ => &[BossState::ChargeWindup, BossState::Shockwave, BossState::Summon],
+    };
+    pattern[attack_count as usize % pattern.len()]
+}
+
+fn enter_state(boss: &mut Boss, state: BossState) {
+    let duration = match state {
+        BossState::Chase => CHASE_TIME / (1.0 + 0.25 * (boss.phase - 1) as f32),
+        BossState::ChargeWindup => WINDUP_TIME,
+        BossState::Charging => CHARGE_TIME,
+        BossState::Shockwave | BossState::Summon => 0.0,
+    };
+    boss.state = state;
+    boss.state_timer = Timer::from_seconds(duration, TimerMode::Once);
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_shockwaves(
+    mut commands: Commands,
+    time: Res<Time>,
+    sound_assets: Res<SoundAssets>,
+    mut ev_shake: EventWriter<ScreenShakeEvent>,
+    mut ev_damaged: EventWriter<PlayerDamaged>,
+    mut wave_query: Query<(Entity, &Transform, &mut Sprite, &mut Shockwave)>,
+    mut player_query: Query<(Entity, &Transform, &mut Health, Option<&Invulnerable>), (With<Player>, Without<Shockwave>)>,
+) {
+    let Ok((player_entity, player_transform, mut player_health, invulnerable)) = player_query.get_single_mut() else { return };
+    let player_pos = player_transform.translation.truncate();
+    // Hai vòng sóng cùng quét qua trong một frame cũng chỉ tính một đòn
+    let mut is_invulnerable = invulnerable.is_some();
+
+    for (entity, transform, mut sprite, mut wave) in wave_query.iter_mut() {
+        wave.radius += wave.speed * time.delta_seconds();
+        sprite.custom_size = Some(Vec2::splat(wave.radius * 2.0));
+
+        let distance = player_pos.distance(transform.translation.truncate());
+        if !wave.has_hit && !is_invulnerable && (distance - wave.radius).abs() < SHOCKWAVE_THICKNESS {
+            wave.has_hit = true;
+            is_invulnerable = true;
+            commands.entity(player_entity).insert(Invulnerable::new(PLAYER_IFRAME_TIME));
+            player_health.current -= wave.damage;
+            ev_damaged.send(PlayerDamaged { position: player_pos, amount: wave.damage });
+
+            commands.spawn(AudioBundle {
+                source: sound_assets.hurt.clone(),
+                settings: PlaybackSettings::DESPAWN,
+            });
+            ev_shake.send(ScreenShakeEvent { intensity: 3.0, duration: 0.1 });
+        }
+
+        if wave.radius >= wave.max_radius {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn cleanup_shockwaves(
+    mut commands: Commands,
+    query: Query<Entity, With<Shockwave>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -4,6 +4,7 @@ use crate::resources::game_stats::GameStats;
 use crate::components::stats::PlayerStats;
 use crate::components::player::Player;
 use crate::resources::level::LevelManager;
+use crate::resources::relics::RelicInventory;
 use crate::resources::sound::SoundAssets;
 
 const TITLE_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
@@ -25,6 +26,7 @@ pub fn setup_game_over(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     game_stats: Res<GameStats>,
+    relics: Res<RelicInventory>,
     player_query: Query<&PlayerStats, With<Player>>,
     sound_assets: Res<SoundAssets>,
 ) {
@@ -33,13 +35,14 @@ pub fn setup_game_over(
         settings: PlaybackSettings::DESPAWN, 
     });
 
-    spawn_endgame_screen(commands, asset_server, game_stats, player_query, "GAME OVER", Color::srgb(1.0, 0.0, 0.0));
+    spawn_endgame_screen(commands, asset_server, game_stats, relics, player_query, "GAME OVER", Color::srgb(1.0, 0.0, 0.0));
 }
 
 pub fn setup_victory(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     game_stats: Res<GameStats>,
+    relics: Res<RelicInventory>,
     player_query: Query<&PlayerStats, With<Player>>,
     sound_assets: Res<SoundAssets>
 ) {
@@ -47,13 +50,14 @@ pub fn setup_victory(
         source: sound_assets.victory.clone(),
         settings: PlaybackSettings::DESPAWN,
     });
-    spawn_endgame_screen(commands, asset_server, game_stats, player_query, "VICTORY!", TITLE_COLOR);
+    spawn_endgame_screen(commands, asset_server, game_stats, relics, player_query, "VICTORY!", TITLE_COLOR);
 }
 
 fn spawn_endgame_screen(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     game_stats: Res<GameStats>,
+    relics: Res<RelicInventory>,
     player_query: Query<&PlayerStats, With<Player>>,
     title: &str,
     title_color: Color,
@@ -107,6 +111,17 @@ fn spawn_endgame_screen(
                 ..default()
             }));
 
+            if let Some(reason) = &game_stats.end_reason {
+                board.spawn(TextBundle::from_section(
+                    reason.clone(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 18.0,
+                        color: title_color,
+                    },
+                ));
+            }
+
             // STATS
             let stats_style = TextStyle {
                 font: font.clone(),
@@ -124,11 +139,26 @@ fn spawn_endgame_screen(
                 stats_style.clone(),
             ));
 
+            board.spawn(TextBundle::from_section(
+                format!("PARRIES: {}", game_stats.parries),
+                stats_style.clone(),
+            ));
+
             board.spawn(TextBundle::from_section(
                 format!("MONEY: {}", money),
                 stats_style.clone(),
             ));
 
+            board.spawn(TextBundle::from_section(
+                format!("RELICS: {}", relics.names()),
+                stats_style.clone(),
+            ));
+
+            board.spawn(TextBundle::from_section(
+                format!("KILLS: {}  +{} SHARDS", game_stats.kills, game_stats.shards_earned),
+                stats_style.clone(),
+            ));
+
             // BUTTONS
             spawn_button(board, &font, "RESTART", EndgameButtonAction::Restart);
             spawn_button(board, &font, "MENU", EndgameButtonAction::Menu);
@@ -168,6 +198,7 @@ fn spawn_button(
     });
 }
 
+#[allow(clippy::type_complexity)]
 pub fn endgame_action(
     mut commands: Commands,
     sound_assets: Res<SoundAssets>,
@@ -1,5 +1,25 @@
 use bevy::prelude::*;
-use crate::components::enemy::{Enemy, EnemyAnimationTimer, EnemySpeed};
+use crate::components::enemy::{Enemy, EnemyAnimationTimer, EnemySpeed, CrowdAgent, RangedAttack, Shield, ShieldVisual};
+use crate::components::collider::Collider;
+use crate::components::player::Player;
+use crate::components::boss::Boss;
+use crate::level::level_grid::LevelGrid;
+use crate::resources::flow_field::FlowField;
+use crate::resources::game_config::CrowdSettings;
+
+const WALL_AVOID_WEIGHT: f32 = 1.5;
+
+// Quái bắt đầu tránh nhau khi khoảng cách < tổng bán kính * hệ số này
+const SEPARATION_RANGE: f32 = 1.3;
+// Chỉ bọc sườn khi còn ở xa, lại gần thì lao thẳng vào player
+const FLANK_MIN_DISTANCE: f32 = 120.0;
+const FLANK_FALLOFF: f32 = 200.0;
+const SOFT_PUSH_STRENGTH: f32 = 0.5;
+// Quái bắn xa lùi lại khi player áp sát hơn tỉ lệ này của khoảng cách mong muốn
+const RANGED_RETREAT_RATIO: f32 = 0.7;
+// Tốc độ xoay khiên (rad/s), đủ chậm để player có cơ hội bắn vòng ra sau
+const SHIELD_TURN_SPEED: f32 = 3.0;
+const SHIELD_OFFSET: f32 = 16.0;
 
 pub fn animate_enemies(
     time: Res<Time>,
@@ -7,26 +27,162 @@ pub fn animate_enemies(
 ) {
     for (mut timer, mut atlas) in query.iter_mut() {
         timer.timer.tick(time.delta());
-        if timer.timer.just_finished() {  
-            atlas.index = (atlas.index + 1) % 4;
+        if timer.timer.just_finished() {
+            atlas.index = (atlas.index + 1) % timer.frames;
+        }
+    }
+}
+
+pub fn update_flow_field(
+    grid: Option<Res<LevelGrid>>,
+    mut flow_field: ResMut<FlowField>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let Some(grid) = grid else {
+        if flow_field.grid.is_some() {
+            flow_field.clear();
         }
+        return;
+    };
+    let Ok(player_transform) = player_query.get_single() else { return };
+
+    let player_tile = grid.world_to_tile(player_transform.translation.truncate());
+    if grid.is_wall(player_tile) {
+        return;
+    }
+
+    if grid.is_changed() || flow_field.target != Some(player_tile) {
+        flow_field.rebuild(&grid, player_tile);
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn enemy_movement(
     time: Res<Time>,
-    mut enemy_query: Query<(&mut Transform, &mut Sprite, &EnemySpeed), With<Enemy>>, 
-    player_query: Query<&Transform, (With<crate::components::player::Player>, Without<Enemy>)>,
+    flow_field: Res<FlowField>,
+    crowd: Res<CrowdSettings>,
+    grid: Option<Res<LevelGrid>>,
+    mut enemy_query: Query<(Entity, &mut Transform, &mut Sprite, &EnemySpeed, &Collider, Option<&CrowdAgent>, Option<&RangedAttack>, Option<&mut Shield>), (With<Enemy>, Without<Boss>)>,
+    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
 ) {
     let Ok(player_transform) = player_query.get_single() else { return };
     let player_pos = player_transform.translation.truncate();
 
-    for (mut enemy_transform, mut sprite, enemy_speed) in enemy_query.iter_mut() {
+    let agents: Vec<(Entity, Vec2, f32)> = enemy_query
+        .iter()
+        .map(|(entity, transform, _, _, collider, _, _, _)| (entity, transform.translation.truncate(), collider.radius()))
+        .collect();
+
+    for (entity, mut enemy_transform, mut sprite, enemy_speed, collider, agent, ranged, shield) in enemy_query.iter_mut() {
         let enemy_pos = enemy_transform.translation.truncate();
-        let direction = (player_pos - enemy_pos).normalize_or_zero();
+        let to_player = player_pos - enemy_pos;
+        let distance = to_player.length();
+        let flank_side = agent.map_or(1.0, |agent| agent.flank_side);
+
+        let mut direction = match ranged {
+            // Giữ khoảng cách: quá gần thì lùi, trong tầm thì đi vòng quanh player
+            Some(ranged) if distance < ranged.preferred_distance * RANGED_RETREAT_RATIO => {
+                -to_player.normalize_or_zero()
+            }
+            Some(ranged) if distance < ranged.preferred_distance => {
+                to_player.normalize_or_zero().perp() * flank_side.signum()
+            }
+            _ => {
+                let target = flow_field.waypoint(enemy_pos).unwrap_or(player_pos);
+                let chase = (target - enemy_pos).normalize_or_zero();
+                let flank = ((distance - FLANK_MIN_DISTANCE) / FLANK_FALLOFF).clamp(0.0, 1.0);
+                chase + chase.perp() * flank_side * crowd.flank_weight * flank
+            }
+        };
+
+        direction += separation(entity, enemy_pos, collider.radius(), &agents) * crowd.separation_weight;
+
+        if let Some(grid) = grid.as_deref() {
+            direction += grid.wall_repulsion(enemy_pos, collider.half_size) * WALL_AVOID_WEIGHT;
+        }
+        direction = direction.normalize_or_zero();
 
         enemy_transform.translation += (direction * enemy_speed.speed * time.delta_seconds()).extend(0.0);
 
-        sprite.flip_x = direction.x < 0.0;
+        if let Some(mut shield) = shield {
+            if direction != Vec2::ZERO {
+                let max_turn = SHIELD_TURN_SPEED * time.delta_seconds();
+                let turn = shield.facing.angle_between(direction).clamp(-max_turn, max_turn);
+                shield.facing = Vec2::from_angle(turn).rotate(shield.facing).normalize_or_zero();
+            }
+        }
+
+        if ranged.is_some() {
+            // Quái bắn xa luôn quay mặt về phía player
+            sprite.flip_x = to_player.x < 0.0;
+        } else if direction.x != 0.0 {
+            sprite.flip_x = direction.x < 0.0;
+        }
     }
-}
\ No newline at end of file
+}
+
+pub fn update_shield_visuals(
+    shield_query: Query<&Shield>,
+    mut visual_query: Query<(&Parent, &mut Transform), With<ShieldVisual>>,
+) {
+    for (parent, mut transform) in visual_query.iter_mut() {
+        let Ok(shield) = shield_query.get(parent.get()) else { continue };
+
+        transform.translation = (shield.facing * SHIELD_OFFSET).extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(shield.facing.to_angle());
+    }
+}
+
+// Đẩy nhẹ các quái đang chồng lên nhau ra, chạy trước enemy_wall_collision
+// để tường vẫn là thứ quyết định vị trí cuối cùng.
+pub fn enemy_soft_push(
+    crowd: Res<CrowdSettings>,
+    mut enemy_query: Query<(&mut Transform, &Collider), With<Enemy>>,
+) {
+    if !crowd.soft_push {
+        return;
+    }
+
+    let mut pairs = enemy_query.iter_combinations_mut();
+    while let Some([(mut transform_a, collider_a), (mut transform_b, collider_b)]) = pairs.fetch_next() {
+        let delta = transform_a.translation.truncate() - transform_b.translation.truncate();
+        let min_distance = collider_a.radius() + collider_b.radius();
+        let distance = delta.length();
+
+        if distance >= min_distance {
+            continue;
+        }
+
+        let normal = if distance > 0.0 { delta / distance } else { Vec2::X };
+        let correction = normal * (min_distance - distance) * SOFT_PUSH_STRENGTH * 0.5;
+
+        transform_a.translation += correction.extend(0.0);
+        transform_b.translation -= correction.extend(0.0);
+    }
+}
+
+fn separation(entity: Entity, pos: Vec2, radius: f32, agents: &[(Entity, Vec2, f32)]) -> Vec2 {
+    let mut push = Vec2::ZERO;
+
+    for &(other, other_pos, other_radius) in agents {
+        if other == entity {
+            continue;
+        }
+
+        let range = (radius + other_radius) * SEPARATION_RANGE;
+        let offset = pos - other_pos;
+        let distance = offset.length();
+
+        if distance >= range {
+            continue;
+        }
+
+        if distance > 0.0 {
+            push += offset / distance * (1.0 - distance / range);
+        } else {
+            // Trùng vị trí hoàn toàn: tách theo thứ tự entity cho ổn định
+            push += if entity < other { Vec2::X } else { Vec2::NEG_X };
+        }
+    }
+    push
+}
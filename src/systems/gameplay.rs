@@ -1,26 +1,63 @@
 use bevy::prelude::*;
-use crate::components::item::Coin;
-use crate::components::player::Player;
+use crate::components::item::{Coin, DroppedBullet, RelicPickup};
+use crate::components::player::{Player, AuraEnergy, Dash};
 use crate::components::collider::Collider;
-use crate::components::stats::PlayerStats;
+use crate::components::stats::{PlayerStats, StatKind, Stats};
 use crate::components::enemy::{Health, Enemy};
 use crate::components::bullet::Bullet;
+use crate::events::CoinCollected;
+use crate::level::level_assets::GameAssets;
+use crate::level::level_grid::LevelGrid;
 use crate::utils::math::{check_collision, CollisionSide};
 use crate::resources::game_stats::GameStats;
+use crate::resources::loot::CoinSize;
 use crate::resources::level::LevelManager;
+use crate::resources::profile::Profile;
+use crate::resources::relics::{KillCombo, RelicInventory};
+use crate::resources::run_rng::RunRng;
+use crate::resources::shop_catalog::PurchaseHistory;
 use crate::resources::sound::SoundAssets;
+use crate::states::AppState;
+use crate::systems::loot::spawn_coin;
+
+// Chờ thêm vài giây phòng khi quái chết vì Burn và rơi coin
+const SOFTLOCK_GRACE_TIME: f32 = 5.0;
+const COIN_MAGNET_SPEED: f32 = 250.0;
+// Coin càng gần player càng bị hút nhanh, tối đa gấp đôi
+const COIN_MAGNET_BOOST: f32 = 2.0;
+const COIN_FRICTION: f32 = 4.0;
+const COIN_BLINK_TIME: f32 = 3.0;
+const COIN_BLINK_SPEED: f32 = 8.0;
+// Quá số coin này trên sàn thì gộp các coin nhỏ nhất lại
+const MAX_COINS: usize = 40;
 
 pub fn player_collect_coin(
     mut commands: Commands,
-    mut player_query: Query<(&Transform, &Collider, &mut PlayerStats), With<Player>>,
-    coin_query: Query<(Entity, &Transform, &Collider, &Coin), With<Coin>>,
+    time: Res<Time>,
+    mut player_query: Query<(&Transform, &Collider, &mut PlayerStats, &Stats), With<Player>>,
+    mut coin_query: Query<(Entity, &mut Transform, &Collider, &mut Coin), Without<Player>>,
     sound_assets: Res<SoundAssets>,
+    mut ev_collected: EventWriter<CoinCollected>,
 ) {
-    if let Ok((player_transform, player_collider, mut player_stats)) = player_query.get_single_mut() {
-        for (coin_entity, coin_transform, coin_collider, coin) in coin_query.iter() {
+    if let Ok((player_transform, player_collider, mut player_stats, attributes)) = player_query.get_single_mut() {
+        let player_pos = player_transform.translation.truncate();
+
+        let magnet_range = attributes.get(StatKind::MagnetRange);
+
+        for (coin_entity, mut coin_transform, coin_collider, mut coin) in coin_query.iter_mut() {
+            // Nam châm: coin trong tầm bỏ quán tính văng và tự bay về phía player
+            let to_player = player_pos - coin_transform.translation.truncate();
+            let distance = to_player.length();
+            if distance < magnet_range {
+                coin.velocity = Vec2::ZERO;
+                let boost = 1.0 + (COIN_MAGNET_BOOST - 1.0) * (1.0 - distance / magnet_range);
+                let step = to_player.normalize_or_zero() * COIN_MAGNET_SPEED * boost * time.delta_seconds();
+                coin_transform.translation += step.extend(0.0);
+            }
+
             let collision = check_collision(
                 player_transform, player_collider,
-                coin_transform, coin_collider
+                &coin_transform, coin_collider
             );
 
             if collision != CollisionSide::None {
@@ -29,6 +66,10 @@ pub fn player_collect_coin(
                     settings: PlaybackSettings::DESPAWN.with_volume(bevy::audio::Volume::new(0.5)), 
                 });
                 player_stats.money += coin.value;
+                ev_collected.send(CoinCollected {
+                    position: coin_transform.translation.truncate(),
+                    value: coin.value,
+                });
                 info!("Collected Coin! Value: {} | Total Money: {}", coin.value, player_stats.money);
                 
                 commands.entity(coin_entity).despawn();
@@ -36,6 +77,125 @@ pub fn player_collect_coin(
         }
     }
 }
+
+/// Coin trượt theo vận tốc văng, dừng trước tường, nhấp nháy rồi biến mất khi hết giờ.
+pub fn update_coins(
+    mut commands: Commands,
+    time: Res<Time>,
+    grid: Option<Res<LevelGrid>>,
+    mut coin_query: Query<(Entity, &mut Transform, &Collider, &mut Coin, &mut Sprite)>,
+) {
+    let dt = time.delta_seconds();
+
+    for (entity, mut transform, collider, mut coin, mut sprite) in coin_query.iter_mut() {
+        if coin.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if coin.velocity != Vec2::ZERO {
+            let step = coin.velocity * dt;
+            let target = transform.translation.truncate() + step;
+            if grid.as_ref().is_some_and(|g| g.overlaps_wall(target, collider.half_size)) {
+                coin.velocity = Vec2::ZERO;
+            } else {
+                transform.translation += step.extend(0.0);
+                coin.velocity *= (1.0 - COIN_FRICTION * dt).max(0.0);
+                if coin.velocity.length_squared() < 1.0 {
+                    coin.velocity = Vec2::ZERO;
+                }
+            }
+        }
+
+        let remaining = coin.lifetime.remaining_secs();
+        let visible = remaining > COIN_BLINK_TIME || ((remaining * COIN_BLINK_SPEED) as u32).is_multiple_of(2);
+        sprite.color.set_alpha(if visible { 1.0 } else { 0.2 });
+    }
+}
+
+/// Sàn quá nhiều coin thì gộp các coin giá trị nhỏ nhất thành một coin lớn,
+/// giữ tổng tiền và giới hạn số entity.
+pub fn merge_coins(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    coin_query: Query<(Entity, &Transform, &Coin)>,
+) {
+    let count = coin_query.iter().len();
+    if count <= MAX_COINS {
+        return;
+    }
+
+    let mut coins: Vec<_> = coin_query.iter().collect();
+    coins.sort_by_key(|(_, _, coin)| coin.value);
+    let merged = &coins[..count - MAX_COINS + 1];
+
+    let value: u32 = merged.iter().map(|(_, _, coin)| coin.value).sum();
+    let position = merged
+        .iter()
+        .map(|(_, transform, _)| transform.translation.truncate())
+        .sum::<Vec2>() / merged.len() as f32;
+    for (entity, ..) in merged {
+        commands.entity(*entity).despawn();
+    }
+    spawn_coin(&mut commands, &game_assets, value, position, Vec2::ZERO);
+    debug!("Merged {} coins into one {:?} coin worth {}", merged.len(), CoinSize::for_value(value), value);
+}
+
+pub fn player_collect_dropped_bullet(
+    mut commands: Commands,
+    mut player_query: Query<(&Transform, &Collider, &mut PlayerStats), With<Player>>,
+    dropped_query: Query<(Entity, &Transform, &Collider), With<DroppedBullet>>,
+    sound_assets: Res<SoundAssets>,
+) {
+    let Ok((player_transform, player_collider, mut player_stats)) = player_query.get_single_mut() else { return };
+
+    for (entity, transform, collider) in dropped_query.iter() {
+        if check_collision(player_transform, player_collider, transform, collider) != CollisionSide::None {
+            commands.spawn(AudioBundle {
+                source: sound_assets.item.clone(),
+                settings: PlaybackSettings::DESPAWN,
+            });
+            player_stats.spare_bullets += 1;
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Player không còn đạn, không đủ tiền, trên sàn không còn coin hay vỏ đạn
+/// mà quái vẫn còn: chờ một lúc cho chắc rồi kết thúc run với thông báo rõ ràng.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn detect_ammo_softlock(
+    time: Res<Time>,
+    player_query: Query<(&PlayerStats, &Stats), With<Player>>,
+    bullet_query: Query<(), With<Bullet>>,
+    pickup_query: Query<(), Or<(With<Coin>, With<DroppedBullet>)>>,
+    enemy_query: Query<(), With<Enemy>>,
+    mut game_stats: ResMut<GameStats>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut stuck_timer: Local<Option<Timer>>,
+) {
+    let Ok((stats, attributes)) = player_query.get_single() else { return };
+
+    let can_shoot = stats.spare_bullets > 0 || stats.money >= attributes.bullet_cost();
+    let is_stuck = !can_shoot
+        && bullet_query.is_empty()
+        && pickup_query.is_empty()
+        && !enemy_query.is_empty();
+
+    if !is_stuck {
+        *stuck_timer = None;
+        return;
+    }
+
+    let timer = stuck_timer.get_or_insert_with(|| Timer::from_seconds(SOFTLOCK_GRACE_TIME, TimerMode::Once));
+    if timer.tick(time.delta()).finished() {
+        info!("Player is out of ammo with no way to recover. Ending run.");
+        game_stats.end_reason = Some("OUT OF AMMO".to_string());
+        next_state.set(AppState::GameOver);
+        *stuck_timer = None;
+    }
+}
+
 pub fn reset_player_position(
     mut player_query: Query<&mut Transform, With<Player>>,
 ) {
@@ -45,20 +205,33 @@ pub fn reset_player_position(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn reset_game_state(
-    mut player_query: Query<(&mut Health, &mut PlayerStats), With<Player>>,
+    mut player_query: Query<(&mut Health, &mut PlayerStats, &mut Stats, &mut Dash, &mut AuraEnergy), With<Player>>,
     mut game_stats: ResMut<GameStats>,
     mut level_manager: ResMut<LevelManager>, 
+    mut history: ResMut<PurchaseHistory>,
+    mut relics: ResMut<RelicInventory>,
+    mut combo: ResMut<KillCombo>,
+    mut rng: ResMut<RunRng>,
+    profile: Res<Profile>,
 ) {
-    if let Ok((mut health, mut stats)) = player_query.get_single_mut() {
-        health.current = health.max;
-        stats.current_hp = stats.max_hp;
-        stats.money = 10; 
+    if let Ok((mut health, mut stats, mut attributes, mut dash, mut aura)) = player_query.get_single_mut() {
+        *stats = PlayerStats::default();
+        *attributes = Stats::player();
+        profile.weapon.apply(&mut stats, &mut attributes);
+        *health = Health::new(attributes.get(StatKind::MaxHp));
         info!("Player Stats Reset");
-        stats.damage = 1.0;
+        *dash = Dash::full(&attributes);
+        *aura = AuraEnergy::full(&attributes);
     }
     *game_stats = GameStats::default();
+    *history = PurchaseHistory::default();
+    *relics = RelicInventory::default();
+    *combo = KillCombo::default();
+    *rng = RunRng::from_entropy();
 
+    level_manager.difficulty = profile.difficulty;
     level_manager.load_level(1);
     
     level_manager.current_wave_index = 0;
@@ -78,10 +251,11 @@ pub fn despawn_all_enemies(
     info!("All Enemies Despawned");
 }
 
+#[allow(clippy::type_complexity)]
 pub fn cleanup_level_items(
     mut commands: Commands,
     bullet_query: Query<Entity, With<Bullet>>,
-    coin_query: Query<Entity, With<Coin>>,
+    coin_query: Query<Entity, Or<(With<Coin>, With<DroppedBullet>, With<RelicPickup>)>>,
 ) {
     for entity in bullet_query.iter() {
         commands.entity(entity).despawn_recursive();
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use rand::Rng;
+use std::f32::consts::TAU;
+use crate::components::collider::Collider;
+use crate::components::item::Coin;
+use crate::level::level_assets::GameAssets;
+use crate::resources::game_config::PowerUpTable;
+use crate::resources::loot::{CoinSize, LootDrop, LootTable};
+use crate::resources::run_rng::RunRng;
+use crate::systems::power_up::spawn_power_up_pickup;
+
+// Nhiều món rơi cùng lúc thì xếp vòng quanh chỗ quái chết cho khỏi chồng lên nhau
+const DROP_SPREAD: f32 = 14.0;
+const COIN_SCATTER_SPEED_MIN: f32 = 80.0;
+const COIN_SCATTER_SPEED_MAX: f32 = 160.0;
+const COIN_LIFETIME: f32 = 15.0;
+
+/// Gom những gì cần để rơi đồ theo bảng loot của archetype,
+/// mọi đường giết quái (đạn, Burn, relic) đều đi qua đây.
+#[derive(SystemParam)]
+pub struct LootSpawner<'w> {
+    game_assets: Res<'w, GameAssets>,
+    asset_server: Res<'w, AssetServer>,
+    power_ups: Res<'w, PowerUpTable>,
+    // Loot bốc bằng RNG của run để cùng seed thì rơi giống nhau
+    rng: ResMut<'w, RunRng>,
+}
+
+impl LootSpawner<'_> {
+    pub fn drop(&mut self, commands: &mut Commands, table: &LootTable, position: Vec2, ricochet: bool) {
+        let rng = &mut *self.rng;
+        let drops = table.roll(rng, ricochet);
+        let count = drops.len();
+
+        for (i, drop) in drops.into_iter().enumerate() {
+            let offset = if count > 1 {
+                Vec2::from_angle(TAU * i as f32 / count as f32) * DROP_SPREAD
+            } else {
+                Vec2::ZERO
+            };
+            let drop_pos = position + offset;
+
+            match drop {
+                LootDrop::Nothing => {}
+                LootDrop::Coin(size) => {
+                    // Văng ra theo hướng ngẫu nhiên rồi trượt chậm lại
+                    let angle = rng.gen_range(0.0..TAU);
+                    let speed = rng.gen_range(COIN_SCATTER_SPEED_MIN..COIN_SCATTER_SPEED_MAX);
+                    spawn_coin(commands, &self.game_assets, size.value(), drop_pos, Vec2::from_angle(angle) * speed);
+                }
+                LootDrop::PowerUp => {
+                    if let Some(kind) = self.power_ups.roll(rng) {
+                        spawn_power_up_pickup(commands, &self.asset_server, kind, drop_pos, self.power_ups.lifetime);
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn spawn_coin(commands: &mut Commands, game_assets: &GameAssets, value: u32, position: Vec2, velocity: Vec2) {
+    let size = CoinSize::for_value(value);
+    commands.spawn((
+        SpriteBundle {
+            texture: game_assets.coin_texture.clone(),
+            transform: Transform::from_translation(position.extend(1.0)),
+            sprite: Sprite {
+                color: size.tint(),
+                custom_size: Some(Vec2::splat(size.size())),
+                ..default()
+            },
+            ..default()
+        },
+        Coin::new(value, velocity, COIN_LIFETIME),
+        Collider::new(size.size(), size.size()),
+    ));
+}
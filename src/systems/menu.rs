@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 use crate::states::AppState;
+use crate::resources::save_game::SaveSlot;
 use crate::resources::sound::SoundAssets;
+use crate::systems::profile::UnlockList;
+use crate::systems::settings::{spawn_settings_panel, SettingsPanel};
 
 const FONT_PATH: &str = "fonts/pixel_3.ttf";
 
@@ -12,21 +15,26 @@ const PRESSED_TEXT_COLOR: Color = Color::srgb(0.8, 0.8, 0.0);
 #[derive(Component)]
 pub struct MenuUI;
 
-#[derive(Component)]
-pub struct MainMenuNode;
-
-#[derive(Component)]
-pub struct TutorialNode;
+// Các trang của menu, chỉ một trang hiện tại một thời điểm
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum MenuPage {
+    Main,
+    Tutorial,
+    Unlocks,
+}
 
 #[derive(Component)]
 pub enum MenuButtonAction {
+    Continue,
     Play,
     Tutorial,
+    Unlocks,
+    Settings,
     Exit,
     BackToMenu,
 }
 
-pub fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>, slot: Res<SaveSlot>) {
     let font = asset_server.load(FONT_PATH);
     let bg_image = asset_server.load("sprites/ui/menu_bg.png"); 
 
@@ -99,9 +107,21 @@ pub fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                     },
                     ..default()
                 },
-                MainMenuNode,
+                MenuPage::Main,
             )).with_children(|menu| {
+                // Chỉ hiện khi có run lưu dở đọc được
+                if let Some(run) = &slot.run {
+                    spawn_button(menu, &font, &format!("CONTINUE (LV {})", run.level), MenuButtonAction::Continue, 35.0);
+                }
+                if let Some(notice) = &slot.notice {
+                    menu.spawn(TextBundle::from_section(
+                        notice.as_str(),
+                        TextStyle { font: font.clone(), font_size: 20.0, color: NORMAL_TEXT_COLOR },
+                    ));
+                }
                 spawn_button(menu, &font, "PLAY GAME", MenuButtonAction::Play, 35.0);
+                spawn_button(menu, &font, "UNLOCKS", MenuButtonAction::Unlocks, 35.0);
+                spawn_button(menu, &font, "SETTINGS", MenuButtonAction::Settings, 35.0);
                 spawn_button(menu, &font, "TUTORIAL", MenuButtonAction::Tutorial, 35.0);
                 spawn_button(menu, &font, "EXIT", MenuButtonAction::Exit, 35.0);
             });
@@ -117,7 +137,7 @@ pub fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                     },
                     ..default()
                 },
-                TutorialNode,
+                MenuPage::Tutorial,
             )).with_children(|tutorial| {
                 tutorial.spawn(TextBundle::from_section(
                     "HOW TO PLAY",
@@ -140,6 +160,48 @@ pub fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
             });
         });
     });
+
+    // Trang mở khoá phủ cả màn hình, danh sách bên trong do `refresh_unlock_list` dựng
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.0),
+                display: Display::None,
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.85).into(),
+            z_index: ZIndex::Global(1),
+            ..default()
+        },
+        MenuPage::Unlocks,
+        MenuUI,
+    )).with_children(|page| {
+        page.spawn(TextBundle::from_section(
+            "UNLOCKS",
+            TextStyle { font: font.clone(), font_size: 35.0, color: TITLE_COLOR },
+        ));
+        page.spawn((
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                ..default()
+            },
+            UnlockList,
+        ));
+        spawn_button(page, &font, "BACK", MenuButtonAction::BackToMenu, 30.0);
+    });
+
+    spawn_settings_panel(&mut commands, &font, MenuUI);
 }
 
 fn spawn_button(
@@ -173,6 +235,7 @@ fn spawn_button(
     });
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn menu_action(
     mut commands: Commands,
     sound_assets: Res<SoundAssets>,
@@ -185,8 +248,9 @@ pub fn menu_action(
     
     mut app_exit_events: EventWriter<bevy::app::AppExit>,
     mut next_state: ResMut<NextState<AppState>>,
-    mut menu_node_query: Query<&mut Style, (With<MainMenuNode>, Without<TutorialNode>)>,
-    mut tutorial_node_query: Query<&mut Style, (With<TutorialNode>, Without<MainMenuNode>)>,
+    mut slot: ResMut<SaveSlot>,
+    mut page_query: Query<(&mut Style, &MenuPage)>,
+    mut settings_query: Query<&mut Style, (With<SettingsPanel>, Without<MenuPage>)>,
 ) {
     for (interaction, action, children, mut transform) in &mut interaction_query {
         
@@ -211,25 +275,22 @@ pub fn menu_action(
             });
 
             match action {
+                MenuButtonAction::Continue => {
+                    // Save được lưu lúc vào shop nên chơi tiếp từ shop
+                    slot.resume = true;
+                    next_state.set(AppState::BuffScreen);
+                }
                 MenuButtonAction::Play => {
                     next_state.set(AppState::Playing);
                 }
-                MenuButtonAction::Tutorial => {
-                    if let Ok(mut style) = menu_node_query.get_single_mut() {
-                        style.display = Display::None;
-                    }
-                    if let Ok(mut style) = tutorial_node_query.get_single_mut() {
+                MenuButtonAction::Tutorial => show_page(&mut page_query, MenuPage::Tutorial),
+                MenuButtonAction::Unlocks => show_page(&mut page_query, MenuPage::Unlocks),
+                MenuButtonAction::Settings => {
+                    for mut style in settings_query.iter_mut() {
                         style.display = Display::Flex;
                     }
                 }
-                MenuButtonAction::BackToMenu => {
-                    if let Ok(mut style) = menu_node_query.get_single_mut() {
-                        style.display = Display::Flex;
-                    }
-                    if let Ok(mut style) = tutorial_node_query.get_single_mut() {
-                        style.display = Display::None;
-                    }
-                }
+                MenuButtonAction::BackToMenu => show_page(&mut page_query, MenuPage::Main),
                 MenuButtonAction::Exit => {
                     app_exit_events.send(bevy::app::AppExit::Success);
                 }
@@ -238,6 +299,12 @@ pub fn menu_action(
     }
 }
 
+fn show_page(page_query: &mut Query<(&mut Style, &MenuPage)>, shown: MenuPage) {
+    for (mut style, page) in page_query.iter_mut() {
+        style.display = if *page == shown { Display::Flex } else { Display::None };
+    }
+}
+
 pub fn cleanup_menu(mut commands: Commands, query: Query<Entity, With<MenuUI>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
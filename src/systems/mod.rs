@@ -10,4 +10,14 @@ pub mod wave;
 pub mod timer;
 pub mod menu;
 pub mod endgame;
-pub mod shop;
\ No newline at end of file
+pub mod shop;
+pub mod boss;
+pub mod status;
+pub mod relic;
+pub mod stats;
+pub mod power_up;
+pub mod loot;
+pub mod save;
+pub mod profile;
+pub mod settings;
+pub mod pause;
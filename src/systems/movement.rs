@@ -1,11 +1,95 @@
 use bevy::prelude::*;
-use crate::components::player::{Player, MovementStats};
+use crate::components::collider::Collider;
+use crate::components::player::{Player, Dash, Invulnerable};
+use crate::components::stats::{StatKind, Stats};
+use crate::components::status::StatusEffects;
+use crate::level::level_grid::LevelGrid;
 
+#[allow(clippy::type_complexity)]
 pub fn player_movement(
     time: Res<Time>, 
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Transform, &MovementStats, &mut Sprite), With<Player>>,
+    grid: Option<Res<LevelGrid>>,
+    mut query: Query<(&mut Transform, &Stats, &mut Sprite, &Collider, Option<&mut Dash>, Option<&StatusEffects>), With<Player>>,
 ) {
+    let direction = movement_input(&keyboard_input);
+
+    for (mut transform, stats, mut sprite, collider, dash, status) in query.iter_mut() {
+        if let Some(mut dash) = dash.filter(|dash| dash.is_dashing) {
+            dash.timer.tick(time.delta());
+            let speed = stats.get(StatKind::DashDistance) / dash.timer.duration().as_secs_f32();
+            let step = dash.direction * speed * time.delta_seconds();
+
+            // Đi từng trục và dừng trước ô tường, player_wall_collision lo phần còn lại
+            let blocked = |p: Vec2| grid.as_ref().is_some_and(|g| g.overlaps_wall(p, collider.half_size));
+            let pos = transform.translation.truncate();
+            if !blocked(pos + Vec2::new(step.x, 0.0)) {
+                transform.translation.x += step.x;
+            }
+            let pos = transform.translation.truncate();
+            if !blocked(pos + Vec2::new(0.0, step.y)) {
+                transform.translation.y += step.y;
+            }
+
+            if dash.timer.finished() {
+                dash.is_dashing = false;
+            }
+            continue;
+        }
+
+        // Freeze / Stun nhân thẳng vào tốc độ lúc di chuyển, không ghi đè chỉ số
+        let speed = stats.get(StatKind::MoveSpeed) * status.map_or(1.0, StatusEffects::speed_multiplier);
+        transform.translation += direction.extend(0.0) * speed * time.delta_seconds();
+
+        if direction.x < 0.0 {
+            sprite.flip_x = true;
+        } else if direction.x > 0.0 {
+            sprite.flip_x = false;
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn player_dash_input(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut query: Query<(Entity, &mut Dash, &Stats, &Sprite, Option<&Invulnerable>), With<Player>>,
+) {
+    let Ok((player_entity, mut dash, stats, sprite, invulnerable)) = query.get_single_mut() else { return };
+
+    if dash.charges < stats.count(StatKind::DashCharges) && dash.recharge.tick(time.delta()).finished() {
+        dash.charges += 1;
+        dash.recharge.reset();
+    }
+
+    let pressed = keyboard_input.just_pressed(KeyCode::KeyQ) || mouse_input.just_pressed(MouseButton::Right);
+    if !pressed || dash.is_dashing || dash.charges == 0 {
+        return;
+    }
+
+    // Đứng yên thì dash theo hướng đang quay mặt
+    let direction = movement_input(&keyboard_input);
+    dash.direction = if direction != Vec2::ZERO {
+        direction
+    } else if sprite.flip_x {
+        Vec2::NEG_X
+    } else {
+        Vec2::X
+    };
+    dash.charges -= 1;
+    dash.is_dashing = true;
+    dash.timer.reset();
+
+    // Không rút ngắn i-frame dài hơn đang có (vừa trúng đòn)
+    let dash_time = dash.timer.duration().as_secs_f32();
+    if invulnerable.is_none_or(|i| i.timer.remaining_secs() < dash_time) {
+        commands.entity(player_entity).insert(Invulnerable::without_blink(dash_time));
+    }
+}
+
+fn movement_input(keyboard_input: &ButtonInput<KeyCode>) -> Vec2 {
     let mut direction = Vec2::ZERO;
 
     if keyboard_input.pressed(KeyCode::KeyW) {
@@ -21,17 +105,5 @@ pub fn player_movement(
         direction.x += 1.0;
     }
 
-    if direction.length_squared() > 0.0 {
-        direction = direction.normalize();
-    }
-
-    for (mut transform, stats, mut sprite) in query.iter_mut() {
-        transform.translation += direction.extend(0.0) * stats.speed * time.delta_seconds();
-
-        if direction.x < 0.0 {
-            sprite.flip_x = true;
-        } else if direction.x > 0.0 {
-            sprite.flip_x = false;
-        }
-    }
-}
\ No newline at end of file
+    direction.normalize_or_zero()
+}
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+use crate::resources::pause::PauseState;
+use crate::resources::sound::SoundAssets;
+use crate::states::AppState;
+use crate::systems::settings::{spawn_settings_panel, SettingsPanel};
+
+const FONT_PATH: &str = "fonts/pixel_3.ttf";
+const TITLE_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+const NORMAL_TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const HOVERED_TEXT_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
+
+#[derive(Component)]
+pub struct PauseUI;
+
+#[derive(Component)]
+pub enum PauseButtonAction {
+    Resume,
+    Settings,
+    QuitToMenu,
+}
+
+/// Esc bật / tắt pause trong màn chơi.
+pub fn toggle_pause(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut pause: ResMut<PauseState>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    ui_query: Query<Entity, With<PauseUI>>,
+) {
+    if !keyboard.just_pressed(KeyCode::Escape) {
+        return;
+    }
+
+    if pause.paused {
+        resume(&mut commands, &mut pause, &mut virtual_time, &ui_query);
+    } else {
+        pause.paused = true;
+        virtual_time.pause();
+        spawn_pause_menu(&mut commands, &asset_server);
+    }
+}
+
+fn resume(
+    commands: &mut Commands,
+    pause: &mut PauseState,
+    virtual_time: &mut Time<Virtual>,
+    ui_query: &Query<Entity, With<PauseUI>>,
+) {
+    pause.paused = false;
+    virtual_time.unpause();
+    for entity in ui_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn spawn_pause_menu(commands: &mut Commands, asset_server: &AssetServer) {
+    let font = asset_server.load(FONT_PATH);
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.7).into(),
+            z_index: ZIndex::Global(5),
+            ..default()
+        },
+        PauseUI,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "PAUSED",
+            TextStyle { font: font.clone(), font_size: 50.0, color: TITLE_COLOR },
+        ));
+        spawn_button(parent, &font, "RESUME", PauseButtonAction::Resume);
+        spawn_button(parent, &font, "SETTINGS", PauseButtonAction::Settings);
+        spawn_button(parent, &font, "QUIT TO MENU", PauseButtonAction::QuitToMenu);
+    });
+
+    spawn_settings_panel(commands, &font, PauseUI);
+}
+
+fn spawn_button(parent: &mut ChildBuilder, font: &Handle<Font>, text: &str, action: PauseButtonAction) {
+    parent.spawn((
+        ButtonBundle {
+            style: Style {
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::NONE),
+            ..default()
+        },
+        action,
+    )).with_children(|button| {
+        button.spawn(TextBundle::from_section(
+            text,
+            TextStyle { font: font.clone(), font_size: 30.0, color: NORMAL_TEXT_COLOR },
+        ));
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn pause_action(
+    mut commands: Commands,
+    sound_assets: Res<SoundAssets>,
+    interaction_query: Query<(&Interaction, &PauseButtonAction, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut pause: ResMut<PauseState>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    ui_query: Query<Entity, With<PauseUI>>,
+    mut panel_query: Query<&mut Style, With<SettingsPanel>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, action, children) in interaction_query.iter() {
+        let color = match *interaction {
+            Interaction::None => NORMAL_TEXT_COLOR,
+            _ => HOVERED_TEXT_COLOR,
+        };
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.sections[0].style.color = color;
+            }
+        }
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        commands.spawn(AudioBundle {
+            source: sound_assets.select.clone(),
+            settings: PlaybackSettings::DESPAWN,
+        });
+
+        match action {
+            PauseButtonAction::Resume => resume(&mut commands, &mut pause, &mut virtual_time, &ui_query),
+            PauseButtonAction::Settings => {
+                for mut style in panel_query.iter_mut() {
+                    style.display = Display::Flex;
+                }
+            }
+            // Run vẫn còn save ở lần vào shop gần nhất, Continue được
+            PauseButtonAction::QuitToMenu => next_state.set(AppState::Menu),
+        }
+    }
+}
+
+/// Rời màn chơi khi đang pause (quit, game over) thì trả lại thời gian và dọn UI.
+pub fn clear_pause(
+    mut commands: Commands,
+    mut pause: ResMut<PauseState>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    ui_query: Query<Entity, With<PauseUI>>,
+) {
+    resume(&mut commands, &mut pause, &mut virtual_time, &ui_query);
+}
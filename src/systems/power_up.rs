@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+use crate::components::collider::Collider;
+use crate::components::enemy::Enemy;
+use crate::components::player::{Player, Invulnerable};
+use crate::components::power_up::{ActivePowerUps, PowerUpKind, PowerUpPickup, PowerUpVisual};
+use crate::components::stats::{StatKind, StatModifier, Stats};
+use crate::components::status::{StatusEffect, StatusEffects, StatusKind};
+use crate::resources::sound::SoundAssets;
+use crate::systems::status::apply_status;
+use crate::utils::math::{check_collision, CollisionSide};
+use crate::vfx::HitFlash;
+
+const PICKUP_TEXTURE_PATH: &str = "sprites/ui/aura.png";
+const PICKUP_SIZE: f32 = 24.0;
+// Nhấp nháy trong mấy giây cuối trước khi biến mất
+const PICKUP_BLINK_TIME: f32 = 2.0;
+const PICKUP_BLINK_SPEED: f32 = 10.0;
+
+const SHIELD_BUBBLE_SIZE: f32 = 56.0;
+const SLOW_FIELD_RADIUS: f32 = 180.0;
+const SLOW_FIELD_PULSE: f32 = 0.25;
+const SLOW_FIELD_EFFECT: StatusEffect = StatusEffect::new(StatusKind::Freeze, 0.3, 0.4);
+
+pub fn spawn_power_up_pickup(commands: &mut Commands, asset_server: &AssetServer, kind: PowerUpKind, position: Vec2, lifetime: f32) {
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load(PICKUP_TEXTURE_PATH),
+            transform: Transform::from_translation(position.extend(2.0)),
+            sprite: Sprite {
+                color: kind.color(),
+                custom_size: Some(Vec2::splat(PICKUP_SIZE)),
+                ..default()
+            },
+            ..default()
+        },
+        PowerUpPickup {
+            kind,
+            lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
+        },
+        Collider::new(PICKUP_SIZE, PICKUP_SIZE),
+    ));
+}
+
+pub fn update_power_up_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut PowerUpPickup, &mut Sprite)>,
+) {
+    for (entity, mut pickup, mut sprite) in query.iter_mut() {
+        pickup.lifetime.tick(time.delta());
+
+        if pickup.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let remaining = pickup.lifetime.remaining_secs();
+        let visible = remaining > PICKUP_BLINK_TIME || ((remaining * PICKUP_BLINK_SPEED) as u32).is_multiple_of(2);
+        sprite.color.set_alpha(if visible { 1.0 } else { 0.2 });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn collect_power_ups(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    sound_assets: Res<SoundAssets>,
+    mut player_query: Query<(Entity, &Transform, &Collider, &mut ActivePowerUps, &mut Stats, Option<&Invulnerable>), With<Player>>,
+    pickup_query: Query<(Entity, &Transform, &Collider, &PowerUpPickup), Without<Player>>,
+) {
+    let Ok((player_entity, player_transform, player_collider, mut power_ups, mut stats, invulnerable)) = player_query.get_single_mut() else { return };
+
+    for (entity, transform, collider, pickup) in pickup_query.iter() {
+        if check_collision(player_transform, player_collider, transform, collider) == CollisionSide::None {
+            continue;
+        }
+        commands.spawn(AudioBundle {
+            source: sound_assets.item.clone(),
+            settings: PlaybackSettings::DESPAWN,
+        });
+        commands.entity(entity).despawn();
+
+        let kind = pickup.kind;
+        let duration = kind.duration();
+        let was_active = power_ups.is_active(kind);
+        power_ups.activate(kind);
+
+        // Gỡ modifier cũ cùng nguồn để nhặt lại chỉ làm mới thời gian
+        stats.remove_source(kind.id());
+        match kind {
+            PowerUpKind::RapidFire => {
+                stats.add_modifier(StatModifier::add(StatKind::MaxBullets, 2.0).timed(duration, kind.id()));
+                stats.add_modifier(StatModifier::multiply(StatKind::BulletSpeed, 1.5).timed(duration, kind.id()));
+            }
+            PowerUpKind::DoubleDamage => {
+                stats.add_modifier(StatModifier::multiply(StatKind::Damage, 2.0).timed(duration, kind.id()));
+            }
+            PowerUpKind::Magnet => {
+                stats.add_modifier(StatModifier::multiply(StatKind::MagnetRange, 6.0).timed(duration, kind.id()));
+            }
+            PowerUpKind::ShieldBubble => {
+                if invulnerable.is_none_or(|inv| inv.timer.remaining_secs() < duration) {
+                    commands.entity(player_entity).insert(Invulnerable::without_blink(duration));
+                }
+            }
+            PowerUpKind::SlowField => {}
+        }
+
+        if !was_active {
+            spawn_power_up_visual(&mut commands, &asset_server, player_entity, kind);
+        }
+    }
+}
+
+fn spawn_power_up_visual(commands: &mut Commands, asset_server: &AssetServer, player_entity: Entity, kind: PowerUpKind) {
+    // Player được scale x2 nên kích thước con tính theo hệ toạ độ đã scale
+    let size = match kind {
+        PowerUpKind::ShieldBubble => SHIELD_BUBBLE_SIZE,
+        PowerUpKind::SlowField => SLOW_FIELD_RADIUS,
+        _ => return,
+    };
+
+    let mut color = kind.color();
+    color.set_alpha(0.35);
+    commands.entity(player_entity).with_children(|parent| {
+        parent.spawn((
+            SpriteBundle {
+                texture: asset_server.load(PICKUP_TEXTURE_PATH),
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(size)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, -0.1),
+                ..default()
+            },
+            PowerUpVisual { kind },
+        ));
+    });
+}
+
+/// Đếm ngược power-up đang bật, hết hạn thì gỡ luôn sprite hiển thị.
+/// Modifier trong Stats có timer riêng cùng thời lượng nên tự hết theo.
+pub fn tick_power_ups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut player_query: Query<&mut ActivePowerUps, With<Player>>,
+    visual_query: Query<(Entity, &PowerUpVisual)>,
+) {
+    let Ok(mut power_ups) = player_query.get_single_mut() else { return };
+
+    for power_up in power_ups.active.iter_mut() {
+        power_up.timer.tick(time.delta());
+    }
+    power_ups.active.retain(|p| !p.timer.finished());
+
+    for (entity, visual) in visual_query.iter() {
+        if !power_ups.is_active(visual.kind) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Vùng làm chậm quanh player: định kỳ gắn Freeze ngắn lên quái trong bán kính.
+#[allow(clippy::type_complexity)]
+pub fn slow_field_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<(&Transform, &ActivePowerUps), With<Player>>,
+    mut enemy_query: Query<(Entity, &Transform, &Sprite, Option<&HitFlash>, Option<&mut StatusEffects>), (With<Enemy>, Without<Player>)>,
+    mut pulse: Local<Option<Timer>>,
+) {
+    let Ok((player_transform, power_ups)) = player_query.get_single() else { return };
+    if !power_ups.is_active(PowerUpKind::SlowField) {
+        return;
+    }
+
+    let pulse = pulse.get_or_insert_with(|| Timer::from_seconds(SLOW_FIELD_PULSE, TimerMode::Repeating));
+    if !pulse.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let player_pos = player_transform.translation.truncate();
+    for (entity, transform, sprite, flash, status) in enemy_query.iter_mut() {
+        let offset = transform.translation.truncate() - player_pos;
+        if offset.length() > SLOW_FIELD_RADIUS {
+            continue;
+        }
+        let original_color = flash.map_or(sprite.color, |flash| flash.original_color);
+        apply_status(&mut commands, entity, status, original_color, &[SLOW_FIELD_EFFECT], offset);
+    }
+}
+
+/// Power-up không mang sang màn sau.
+pub fn clear_power_ups(
+    mut commands: Commands,
+    mut player_query: Query<(&mut ActivePowerUps, &mut Stats), With<Player>>,
+    visual_query: Query<Entity, With<PowerUpVisual>>,
+    pickup_query: Query<Entity, With<PowerUpPickup>>,
+) {
+    if let Ok((mut power_ups, mut stats)) = player_query.get_single_mut() {
+        for power_up in power_ups.active.drain(..) {
+            stats.remove_source(power_up.kind.id());
+        }
+    }
+    for entity in visual_query.iter().chain(pickup_query.iter()) {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+use crate::events::EnemyKilled;
+use crate::resources::enemy_archetypes::{EnemyArchetypes, EnemyRoster};
+use crate::resources::game_config::EnemyType;
+use crate::resources::game_stats::GameStats;
+use crate::resources::level::LevelManager;
+use crate::resources::profile::{Difficulty, Profile, StartingWeapon, UnlockKind, UNLOCKS};
+use crate::resources::sound::SoundAssets;
+
+const FONT_PATH: &str = "fonts/pixel_3.ttf";
+const ROW_FONT_SIZE: f32 = 16.0;
+const OWNED_COLOR: Color = Color::srgb(0.4, 1.0, 0.4);
+const AFFORDABLE_COLOR: Color = Color::WHITE;
+const LOCKED_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+const SELECT_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+const ROW_HOVER: Color = Color::srgba(1.0, 1.0, 1.0, 0.1);
+
+/// Danh sách mở khoá trong menu, dựng lại mỗi khi hồ sơ thay đổi.
+#[derive(Component)]
+pub struct UnlockList;
+
+#[derive(Component, Clone, Copy)]
+pub enum ProfileButton {
+    CycleWeapon,
+    CycleDifficulty,
+    // Vị trí trong UNLOCKS
+    Buy(usize),
+}
+
+pub fn count_kills(mut ev_killed: EventReader<EnemyKilled>, mut game_stats: ResMut<GameStats>) {
+    game_stats.kills += ev_killed.read().count() as u32;
+}
+
+/// Cộng kết quả run vào hồ sơ. Chạy trước màn hình kết thúc để hiện số shard nhận được.
+pub fn award_run_rewards(
+    mut profile: ResMut<Profile>,
+    mut game_stats: ResMut<GameStats>,
+    level_manager: Res<LevelManager>,
+) {
+    let levels = level_manager.current_level.saturating_sub(1) as u32 + level_manager.level_completed as u32;
+    game_stats.shards_earned = profile.record_run(game_stats.kills, levels, level_manager.difficulty);
+    profile.save();
+    info!("Run rewarded {} shards ({} kills, {} levels)", game_stats.shards_earned, game_stats.kills, levels);
+}
+
+pub fn refresh_unlock_list(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    profile: Res<Profile>,
+    archetypes: Res<EnemyArchetypes>,
+    rosters: Res<Assets<EnemyRoster>>,
+    list_query: Query<(Entity, Ref<UnlockList>)>,
+) {
+    let Ok((list, marker)) = list_query.get_single() else { return };
+    if !marker.is_added() && !profile.is_changed() && !rosters.is_changed() {
+        return;
+    }
+
+    let font = asset_server.load(FONT_PATH);
+    commands.entity(list).despawn_descendants();
+    commands.entity(list).with_children(|parent| {
+        let style = |color| TextStyle { font: font.clone(), font_size: ROW_FONT_SIZE, color };
+
+        parent.spawn(TextBundle::from_section(
+            format!("SHARDS: {}   KILLS: {}   LEVELS: {}", profile.shards, profile.total_kills, profile.levels_cleared),
+            style(SELECT_COLOR),
+        ));
+        spawn_row(parent, style(AFFORDABLE_COLOR), format!("WEAPON: < {} >", profile.weapon.name()), ProfileButton::CycleWeapon);
+        spawn_row(parent, style(AFFORDABLE_COLOR), format!("DIFFICULTY: < {} >", profile.difficulty.name()), ProfileButton::CycleDifficulty);
+
+        for (index, unlock) in UNLOCKS.iter().enumerate() {
+            let (label, color) = if profile.is_unlocked(unlock.id) {
+                let detail = match unlock.kind {
+                    UnlockKind::Codex(id) => archetypes
+                        .get(&rosters, &EnemyType(id.to_string()))
+                        .map(|a| format!("HP {} SPD {} DMG {}", a.hp, a.speed, a.damage))
+                        .unwrap_or_else(|| "...".to_string()),
+                    _ => "OWNED".to_string(),
+                };
+                (format!("{}: {}", unlock.name, detail), OWNED_COLOR)
+            } else if profile.shards >= unlock.cost {
+                (format!("{} - {}", unlock.name, unlock.cost), AFFORDABLE_COLOR)
+            } else {
+                (format!("{} - {}", unlock.name, unlock.cost), LOCKED_COLOR)
+            };
+            spawn_row(parent, style(color), label, ProfileButton::Buy(index));
+        }
+    });
+}
+
+fn spawn_row(parent: &mut ChildBuilder, style: TextStyle, label: String, action: ProfileButton) {
+    parent.spawn((
+        ButtonBundle {
+            style: Style {
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::NONE),
+            ..default()
+        },
+        action,
+    )).with_children(|row| {
+        row.spawn(TextBundle::from_section(label, style));
+    });
+}
+
+pub fn profile_action(
+    mut commands: Commands,
+    sound_assets: Res<SoundAssets>,
+    mut profile: ResMut<Profile>,
+    mut interaction_query: Query<(&Interaction, &ProfileButton, &mut BackgroundColor), Changed<Interaction>>,
+) {
+    for (interaction, action, mut background) in &mut interaction_query {
+        *background = BackgroundColor(match *interaction {
+            Interaction::None => Color::NONE,
+            _ => ROW_HOVER,
+        });
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        commands.spawn(AudioBundle {
+            source: sound_assets.select.clone(),
+            settings: PlaybackSettings::DESPAWN,
+        });
+
+        match *action {
+            ProfileButton::CycleWeapon => {
+                let owned: Vec<StartingWeapon> = StartingWeapon::ALL.into_iter().filter(|w| profile.has_weapon(*w)).collect();
+                profile.weapon = next_in(&owned, profile.weapon);
+            }
+            ProfileButton::CycleDifficulty => {
+                let owned: Vec<Difficulty> = Difficulty::ALL.into_iter().filter(|d| profile.has_difficulty(*d)).collect();
+                profile.difficulty = next_in(&owned, profile.difficulty);
+            }
+            ProfileButton::Buy(index) => {
+                if !profile.buy(&UNLOCKS[index]) {
+                    continue;
+                }
+                info!("Unlocked {}", UNLOCKS[index].name);
+            }
+        }
+        profile.save();
+    }
+}
+
+// Lựa chọn kế tiếp trong danh sách, quay vòng về đầu
+fn next_in<T: Copy + PartialEq>(options: &[T], current: T) -> T {
+    let index = options.iter().position(|o| *o == current).map_or(0, |i| (i + 1) % options.len());
+    options.get(index).copied().unwrap_or(current)
+}
@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::f32::consts::TAU;
+use crate::components::boss::Boss;
+use crate::components::bullet::{Bullet, Faction};
+use crate::components::collider::Collider;
+use crate::components::enemy::{Enemy, EnemyLoot, Health, Splitter};
+use crate::components::item::RelicPickup;
+use crate::components::particle::{Lifetime, Particle, Velocity};
+use crate::components::player::Player;
+use crate::components::status::{StatusEffect, StatusEffects, StatusKind};
+use crate::events::{BulletBounced, CoinCollected, EnemyKilled, PlayerDamaged};
+use crate::resources::relics::{KillCombo, RelicInventory, RelicKind};
+use crate::resources::run_rng::RunRng;
+use crate::resources::shop_catalog::PurchaseHistory;
+use crate::resources::sound::SoundAssets;
+use crate::systems::ricochet::{EnemyKiller, KillCause};
+use crate::systems::shooting::BulletAssets;
+use crate::systems::status::apply_status;
+use crate::utils::math::{check_collision, CollisionSide};
+use crate::vfx::HitFlash;
+
+const SHARD_EVERY: u32 = 3;
+const SHARD_ANGLE: f32 = 0.5;
+const SHARD_DAMAGE: f32 = 0.5;
+const SHARD_SPEED: f32 = 300.0;
+const SHARD_LIFETIME: f32 = 1.5;
+const SHARD_COLOR: Color = Color::srgb(0.6, 0.9, 1.0);
+
+const COMBO_TARGET: u32 = 5;
+const COMBO_HEAL: f32 = 0.5;
+
+const COIN_BLAST_RADIUS: f32 = 90.0;
+// Sát thương nổ mỗi đơn vị giá trị coin
+const COIN_BLAST_DAMAGE: f32 = 1.0;
+const COIN_BLAST_PARTICLES: usize = 12;
+const COIN_BLAST_COLOR: Color = Color::srgb(1.0, 0.7, 0.2);
+
+const SPITE_RADIUS: f32 = 150.0;
+const SPITE_KNOCKBACK: StatusEffect = StatusEffect::new(StatusKind::Knockback, 0.3, 600.0);
+
+const RELIC_PICKUP_PATH: &str = "sprites/ui/aura.png";
+const RELIC_PICKUP_SIZE: f32 = 28.0;
+
+/// Shard Prism: đạn của player cứ nảy đủ 3 lần thì tách ra hai mảnh nhỏ.
+/// Mảnh không có chủ nên không tính vào giới hạn đạn và không tự tách tiếp.
+pub fn relic_bounce_shards(
+    mut commands: Commands,
+    mut ev_bounced: EventReader<BulletBounced>,
+    relics: Res<RelicInventory>,
+    bullet_assets: Res<BulletAssets>,
+    player_query: Query<Entity, With<Player>>,
+) {
+    if !relics.has(RelicKind::ShardPrism) {
+        ev_bounced.clear();
+        return;
+    }
+    let Ok(player_entity) = player_query.get_single() else { return };
+
+    for event in ev_bounced.read() {
+        if event.owner != Some(player_entity) || !event.bounces.is_multiple_of(SHARD_EVERY) {
+            continue;
+        }
+
+        for angle in [-SHARD_ANGLE, SHARD_ANGLE] {
+            commands.spawn((
+                SpriteBundle {
+                    texture: bullet_assets.texture.clone(),
+                    transform: Transform::from_translation(event.position.extend(5.0))
+                        .with_scale(Vec3::splat(1.2)),
+                    sprite: Sprite {
+                        color: SHARD_COLOR,
+                        ..default()
+                    },
+                    ..default()
+                },
+                TextureAtlas {
+                    layout: bullet_assets.layout.clone(),
+                    index: 0,
+                },
+                Bullet {
+                    direction: Vec2::from_angle(angle).rotate(event.direction),
+                    speed: SHARD_SPEED,
+                    lifetime: Timer::from_seconds(SHARD_LIFETIME, TimerMode::Once),
+                    damage: SHARD_DAMAGE,
+                    faction: Faction::Player,
+                    // Sinh ra từ cú nảy nên đánh được điểm yếu của boss
+                    bounces: 1,
+                    ..default()
+                },
+                Collider::new(8.0, 8.0),
+            ));
+        }
+    }
+}
+
+/// Đếm chuỗi giết, Blood Combo hồi máu mỗi khi chuỗi chạm mốc.
+pub fn relic_kill_combo(
+    time: Res<Time>,
+    mut ev_killed: EventReader<EnemyKilled>,
+    relics: Res<RelicInventory>,
+    mut combo: ResMut<KillCombo>,
+    mut player_query: Query<&mut Health, With<Player>>,
+) {
+    if combo.timer.tick(time.delta()).just_finished() {
+        combo.count = 0;
+    }
+
+    for _ in ev_killed.read() {
+        combo.count += 1;
+        combo.timer.reset();
+
+        if !combo.count.is_multiple_of(COMBO_TARGET) || !relics.has(RelicKind::BloodCombo) {
+            continue;
+        }
+        if let Ok(mut health) = player_query.get_single_mut() {
+            health.heal(COMBO_HEAL);
+        }
+    }
+}
+
+/// Volatile Coins: nhặt coin gây nổ sát thương quái xung quanh.
+#[allow(clippy::type_complexity)]
+pub fn relic_coin_blast(
+    mut commands: Commands,
+    mut ev_collected: EventReader<CoinCollected>,
+    relics: Res<RelicInventory>,
+    mut killer: EnemyKiller,
+    // Boss chỉ nhận sát thương từ đạn nảy trúng điểm yếu
+    mut enemy_query: Query<(Entity, &Transform, &mut Health, Option<&Splitter>, &EnemyLoot), (With<Enemy>, Without<Boss>)>,
+) {
+    if !relics.has(RelicKind::VolatileCoins) {
+        ev_collected.clear();
+        return;
+    }
+
+    for event in ev_collected.read() {
+        spawn_blast_particles(&mut commands, event.position);
+
+        for (entity, transform, mut health, splitter, loot) in enemy_query.iter_mut() {
+            if health.current <= 0.0 || transform.translation.truncate().distance(event.position) > COIN_BLAST_RADIUS {
+                continue;
+            }
+            health.current -= COIN_BLAST_DAMAGE * event.value as f32;
+            if health.current <= 0.0 {
+                killer.kill(entity, transform, splitter, loot, KillCause::Relic);
+            }
+        }
+    }
+}
+
+/// Spite Plate: bị đánh trúng thì đẩy lùi mọi quái ở gần, boss đứng vững.
+#[allow(clippy::type_complexity)]
+pub fn relic_spite_knockback(
+    mut commands: Commands,
+    mut ev_damaged: EventReader<PlayerDamaged>,
+    relics: Res<RelicInventory>,
+    mut enemy_query: Query<(Entity, &Transform, &Sprite, Option<&HitFlash>, Option<&mut StatusEffects>), (With<Enemy>, Without<Boss>)>,
+) {
+    if !relics.has(RelicKind::SpitePlate) {
+        ev_damaged.clear();
+        return;
+    }
+
+    for event in ev_damaged.read() {
+        // Đòn càng đau thì đẩy càng mạnh
+        let knockback = StatusEffect { potency: SPITE_KNOCKBACK.potency * event.amount.max(1.0), ..SPITE_KNOCKBACK };
+        for (entity, transform, sprite, flash, status) in enemy_query.iter_mut() {
+            let offset = transform.translation.truncate() - event.position;
+            if offset.length() > SPITE_RADIUS {
+                continue;
+            }
+            let original_color = flash.map_or(sprite.color, |flash| flash.original_color);
+            apply_status(&mut commands, entity, status, original_color, &[knockback], offset.normalize_or(Vec2::Y));
+        }
+    }
+}
+
+/// Hạ boss thì rơi một relic chưa có, chọn bằng RNG của run.
+pub fn drop_boss_relic(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut ev_killed: EventReader<EnemyKilled>,
+    relics: Res<RelicInventory>,
+    history: Res<PurchaseHistory>,
+    pickup_query: Query<&RelicPickup>,
+    mut rng: ResMut<RunRng>,
+) {
+    for event in ev_killed.read() {
+        if !event.is_boss {
+            continue;
+        }
+
+        let candidates: Vec<RelicKind> = RelicKind::ALL
+            .into_iter()
+            .filter(|relic| !relics.has(*relic) && history.count(relic.id()) == 0)
+            .filter(|relic| pickup_query.iter().all(|pickup| pickup.relic != *relic))
+            .collect();
+        let Some(&relic) = candidates.choose(&mut *rng) else { continue };
+
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.load(RELIC_PICKUP_PATH),
+                transform: Transform::from_translation(event.position.extend(2.0)),
+                sprite: Sprite {
+                    color: relic.color(),
+                    custom_size: Some(Vec2::splat(RELIC_PICKUP_SIZE)),
+                    ..default()
+                },
+                ..default()
+            },
+            RelicPickup { relic },
+            Collider::new(RELIC_PICKUP_SIZE, RELIC_PICKUP_SIZE),
+        ));
+    }
+}
+
+pub fn collect_relic_pickup(
+    mut commands: Commands,
+    sound_assets: Res<SoundAssets>,
+    mut relics: ResMut<RelicInventory>,
+    mut history: ResMut<PurchaseHistory>,
+    player_query: Query<(&Transform, &Collider), With<Player>>,
+    pickup_query: Query<(Entity, &Transform, &Collider, &RelicPickup)>,
+) {
+    let Ok((player_transform, player_collider)) = player_query.get_single() else { return };
+
+    for (entity, transform, collider, pickup) in pickup_query.iter() {
+        if check_collision(player_transform, player_collider, transform, collider) == CollisionSide::None {
+            continue;
+        }
+        commands.spawn(AudioBundle {
+            source: sound_assets.item.clone(),
+            settings: PlaybackSettings::DESPAWN,
+        });
+        relics.add(pickup.relic);
+        // Ghi vào lịch sử để shop không bán lại relic này
+        history.record(pickup.relic.id());
+        info!("Picked up relic: {}", pickup.relic.name());
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn_blast_particles(commands: &mut Commands, position: Vec2) {
+    let mut rng = rand::thread_rng();
+
+    for i in 0..COIN_BLAST_PARTICLES {
+        let angle = TAU * i as f32 / COIN_BLAST_PARTICLES as f32;
+        let speed = rng.gen_range(150.0..250.0);
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: COIN_BLAST_COLOR,
+                    custom_size: Some(Vec2::splat(6.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(3.0)),
+                ..default()
+            },
+            Particle,
+            Velocity(Vec2::from_angle(angle) * speed),
+            Lifetime::new(0.3),
+        ));
+    }
+}
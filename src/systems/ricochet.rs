@@ -1,15 +1,20 @@
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
 use rand::{Rng, thread_rng}; 
 use std::f32::consts::TAU;
-use crate::components::bullet::Bullet;
+use crate::components::bullet::{Bullet, Faction, Recalling};
 use crate::components::collider::{Collider, Wall};
-use crate::components::item::Coin;
-use crate::components::player::Player;
-use crate::components::enemy::{Enemy, Damage, Health};
-use crate::components::stats::PlayerStats;
+use crate::components::player::{Player, Invulnerable};
+use crate::components::enemy::{Enemy, Damage, Health, Shield, Splitter, EnemyLoot, ContactAttack};
+use crate::components::boss::{Boss, WeakPoint};
+use crate::components::status::{StatusEffect, StatusEffects, StatusKind};
 use crate::components::particle::{Particle, Velocity, Lifetime};
-use crate::level::level_assets::GameAssets;
+use crate::events::{BulletBounced, EnemyKilled, PlayerDamaged};
 use crate::resources::sound::SoundAssets;
+use crate::systems::boss::update_shockwaves;
+use crate::systems::status::apply_status;
+use crate::systems::loot::LootSpawner;
+use crate::systems::wave::EnemySpawner;
 use crate::utils::math::{check_collision, CollisionSide};
 use crate::vfx::{HitFlash, ScreenShakeEvent};
 
@@ -18,15 +23,22 @@ const PARTICLE_SIZE: f32 = 6.0;
 const PARTICLE_SPEED_MIN: f32 = 50.0;  
 const PARTICLE_SPEED_MAX: f32 = 150.0;
 const PARTICLE_LIFETIME: f32 = 0.5;
-const DROP_RATE: f64 = 0.5;
-
+// Đạn tới trong góc ±60° quanh hướng khiên sẽ bị dội lại
+const SHIELD_ARC_COS: f32 = 0.5;
+const SPLIT_SPREAD: f32 = 20.0;
+pub const PLAYER_IFRAME_TIME: f32 = 1.0;
+const PLAYER_KNOCKBACK: StatusEffect = StatusEffect::new(StatusKind::Knockback, 0.2, 500.0);
+const ENEMY_KNOCKBACK: StatusEffect = StatusEffect::new(StatusKind::Knockback, 0.25, 350.0);
+
+#[allow(clippy::type_complexity)]
 pub fn bullet_ricochet(
     mut commands: Commands,
     sound_assets: Res<SoundAssets>,
-    mut bullet_query: Query<(&mut Transform, &Collider, &mut Bullet), Without<Wall>>,
+    mut ev_bounced: EventWriter<BulletBounced>,
+    mut bullet_query: Query<(Entity, &mut Transform, &Collider, &mut Bullet, Option<&Recalling>), Without<Wall>>,
     wall_query: Query<(&Transform, &Collider), With<Wall>>,
 ) {
-    for (mut bullet_transform, bullet_collider, mut bullet) in bullet_query.iter_mut() {
+    for (entity, mut bullet_transform, bullet_collider, mut bullet, recalling) in bullet_query.iter_mut() {
         let mut has_reflected = false;
         
         for (wall_transform, wall_collider) in wall_query.iter() {
@@ -45,11 +57,6 @@ pub fn bullet_ricochet(
                 continue;
             }
 
-            commands.spawn(AudioBundle {
-                source: sound_assets.bounce.clone(), 
-                settings: PlaybackSettings::DESPAWN,   
-            });
-
             let offset_x = wall_collider.half_size.x + bullet_collider.half_size.x + 1.0;
             let offset_y = wall_collider.half_size.y + bullet_collider.half_size.y + 1.0;
 
@@ -72,11 +79,34 @@ pub fn bullet_ricochet(
                 },
                 CollisionSide::None => {}
             }
-            bullet.hit_entities.clear();
             has_reflected = true;
+
+            // Đạn đang gọi về mà đụng tường thì huỷ gọi về, không tính là một lần nảy
+            // (nếu không nó sẽ bị lái lại vào tường và "nảy" mỗi frame)
+            if recalling.is_some() {
+                commands.entity(entity).remove::<Recalling>();
+                continue;
+            }
+
+            commands.spawn(AudioBundle {
+                source: sound_assets.bounce.clone(), 
+                settings: PlaybackSettings::DESPAWN,   
+            });
+            bullet.hit_entities.clear();
+            bullet.bounces += 1;
+
+            if bullet.faction == Faction::Player {
+                ev_bounced.send(BulletBounced {
+                    owner: bullet.owner,
+                    position: bullet_transform.translation.truncate(),
+                    direction: bullet.direction,
+                    bounces: bullet.bounces,
+                });
+            }
         }
     }
 }
+#[allow(clippy::type_complexity)]
 pub fn player_wall_collision(
     mut player_query: Query<(&mut Transform, &Collider), (With<Player>, Without<Wall>)>,
     wall_query: Query<(&Transform, &Collider), With<Wall>>,
@@ -117,6 +147,7 @@ pub fn player_wall_collision(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn enemy_wall_collision(
     mut enemy_query: Query<(&mut Transform, &Collider), (With<Enemy>, Without<Wall>)>,
     wall_query: Query<(&Transform, &Collider), With<Wall>>,
@@ -156,21 +187,81 @@ pub fn enemy_wall_collision(
     }
 }
 
+/// Quái chết vì nguồn sát thương nào.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KillCause {
+    Bullet,
+    // Đạn đã nảy qua tường, được bốc thêm loot
+    Ricochet,
+    Status,
+    Relic,
+}
+
+/// Gom những gì cần khi một con quái chết: hiệu ứng, quái con của Splitter
+/// và rơi đồ. Dùng chung cho đạn và các nguồn sát thương khác (Burn).
+#[derive(SystemParam)]
+pub struct EnemyKiller<'w, 's> {
+    commands: Commands<'w, 's>,
+    ev_shake: EventWriter<'w, ScreenShakeEvent>,
+    ev_killed: EventWriter<'w, EnemyKilled>,
+    sound_assets: Res<'w, SoundAssets>,
+    spawner: EnemySpawner<'w>,
+    loot: LootSpawner<'w>,
+    bosses: Query<'w, 's, (), With<Boss>>,
+}
+
+impl EnemyKiller<'_, '_> {
+    pub fn kill(&mut self, enemy_entity: Entity, enemy_transform: &Transform, splitter: Option<&Splitter>, loot: &EnemyLoot, cause: KillCause) {
+        self.ev_killed.send(EnemyKilled {
+            position: enemy_transform.translation.truncate(),
+            is_boss: self.bosses.contains(enemy_entity),
+        });
+
+        self.ev_shake.send(ScreenShakeEvent { 
+            intensity: 2.0, 
+            duration: 0.1 
+        });
+        self.commands.spawn(AudioBundle {
+            source: self.sound_assets.hurt.clone(),
+            settings: PlaybackSettings::DESPAWN, 
+        });
+        spawn_death_particles(&mut self.commands, enemy_transform.translation);
+        self.commands.entity(enemy_entity).despawn_recursive();
+
+        if let Some(splitter) = splitter {
+            let center = enemy_transform.translation.truncate();
+            for i in 0..splitter.children {
+                let angle = TAU * i as f32 / splitter.children as f32;
+                self.spawner.spawn(
+                    &mut self.commands,
+                    &splitter.child,
+                    splitter.difficulty_multiplier,
+                    center + Vec2::from_angle(angle) * SPLIT_SPREAD,
+                );
+            }
+        }
+        self.loot.drop(&mut self.commands, &loot.table, enemy_transform.translation.truncate(), cause == KillCause::Ricochet);
+        println!("Enemy Killed!");
+    }
+}
+
+#[allow(clippy::type_complexity)]
 pub fn bullet_enemy_collision(
     mut commands: Commands,
-    mut bullet_query: Query<(Entity, &Transform, &Collider, &mut Bullet), With<Bullet>>,
-    mut enemy_query: Query<(Entity, &Transform, &Collider, &mut Health, &mut Sprite, Option<&mut HitFlash>), With<Enemy>>,
-    mut ev_shake: EventWriter<ScreenShakeEvent>,
-    game_assets: Res<GameAssets>,
-    sound_assets: Res<SoundAssets>
+    mut bullet_query: Query<(Entity, &mut Transform, &Collider, &mut Bullet), Without<Enemy>>,
+    mut enemy_query: Query<(Entity, &Transform, &Collider, &mut Health, &mut Sprite, Option<&mut HitFlash>, Option<&mut StatusEffects>, Option<&Shield>, Option<&Splitter>, Option<&WeakPoint>, &EnemyLoot), With<Enemy>>,
+    sound_assets: Res<SoundAssets>,
+    mut killer: EnemyKiller,
 ) {
-    let mut rng = rand::thread_rng();
-    
-    for (_, bullet_transform, bullet_collider, mut bullet) in bullet_query.iter_mut() {
+    for (_, mut bullet_transform, bullet_collider, mut bullet) in bullet_query.iter_mut() {
+        if bullet.faction != Faction::Player {
+            continue;
+        }
+
         bullet.hit_entities.retain(|&enemy_id| {
-            if let Ok((_, enemy_transform, enemy_collider, _, _, _)) = enemy_query.get(enemy_id) {
+            if let Ok((_, enemy_transform, enemy_collider, ..)) = enemy_query.get(enemy_id) {
                 let collision = check_collision(
-                    bullet_transform, 
+                    &bullet_transform, 
                     bullet_collider, 
                     enemy_transform, 
                     enemy_collider
@@ -181,14 +272,15 @@ pub fn bullet_enemy_collision(
             }
         });
 
-        for (enemy_entity, enemy_transform, enemy_collider, mut enemy_health, mut sprite, existing_flash) in enemy_query.iter_mut() {
+        for (enemy_entity, enemy_transform, enemy_collider, mut enemy_health, mut sprite, existing_flash, status, shield, splitter, weak_point, loot) in enemy_query.iter_mut() {
             
-            if bullet.hit_entities.contains(&enemy_entity) {
+            // Quái đã chết trong frame này (chờ despawn) thì bỏ qua
+            if bullet.hit_entities.contains(&enemy_entity) || enemy_health.current <= 0.0 {
                 continue;
             }
             
             let collision = check_collision(
-                bullet_transform,
+                &bullet_transform,
                 bullet_collider,
                 enemy_transform,
                 enemy_collider,
@@ -197,8 +289,44 @@ pub fn bullet_enemy_collision(
             if collision != CollisionSide::None {
                 bullet.hit_entities.push(enemy_entity);
 
-                enemy_health.current -= bullet.damage;
+                let enemy_pos = enemy_transform.translation.truncate();
+                let to_bullet = (bullet_transform.translation.truncate() - enemy_pos).normalize_or_zero();
+                let push_out = enemy_collider.radius() + bullet_collider.radius() + 1.0;
+
+                // Dội lại như đập vào tường: trúng khiên thì mặt phản xạ là hướng khiên
+                let deflect_normal = match (shield, weak_point) {
+                    (Some(shield), _) if to_bullet.dot(shield.facing) > SHIELD_ARC_COS => Some(shield.facing),
+                    // Thân boss dội mọi viên, điểm yếu chỉ nhận đạn đã nảy qua tường
+                    (_, Some(weak_point)) if bullet.bounces == 0 || !weak_point.is_hit(to_bullet) => Some(to_bullet),
+                    _ => None,
+                };
+                if let Some(normal) = deflect_normal {
+                    let incoming = bullet.direction.dot(normal);
+                    if incoming < 0.0 {
+                        bullet.direction -= 2.0 * incoming * normal;
+                    }
+                    let new_pos = enemy_pos + to_bullet * push_out;
+                    bullet_transform.translation.x = new_pos.x;
+                    bullet_transform.translation.y = new_pos.y;
+
+                    commands.spawn(AudioBundle {
+                        source: sound_assets.bounce.clone(),
+                        settings: PlaybackSettings::DESPAWN,
+                    });
+                    continue;
+                }
+
+                enemy_health.current -= bullet.hit_damage();
                 println!("Enemy Hit! HP: {}/{}", enemy_health.current, enemy_health.max);
+
+                // Boss miễn nhiễm hiệu ứng để không phá pattern tấn công
+                if !bullet.effects.is_empty() && weak_point.is_none() {
+                    let original_color = existing_flash
+                        .as_ref()
+                        .map_or(sprite.color, |flash| flash.original_color);
+                    apply_status(&mut commands, enemy_entity, status, original_color, &bullet.effects, bullet.direction);
+                }
+
                 if let Some(mut flash) = existing_flash {
                     flash.timer.reset(); 
                 } 
@@ -214,34 +342,8 @@ pub fn bullet_enemy_collision(
                 }
 
                 if enemy_health.current <= 0.0 {
-                    ev_shake.send(ScreenShakeEvent { 
-                        intensity: 2.0, 
-                        duration: 0.1 
-                    });
-                    commands.spawn(AudioBundle {
-                        source: sound_assets.hurt.clone(),
-                        settings: PlaybackSettings::DESPAWN, 
-                    });
-                    spawn_death_particles(&mut commands, enemy_transform.translation);
-                    commands.entity(enemy_entity).despawn_recursive();
-                    if rng.gen_bool(DROP_RATE) {
-                        let coin_transform = Transform::from_translation(enemy_transform.translation)
-                            .with_scale(Vec3::splat(1.0));
-                        commands.spawn((
-                            SpriteBundle {
-                                texture: game_assets.coin_texture.clone(),
-                                transform: coin_transform,
-                                sprite: Sprite {
-                                    custom_size: Some(Vec2::splat(24.0)), 
-                                    ..default()
-                                },
-                                ..default()
-                            },
-                            Coin { value: 1 }, 
-                            Collider::new(24.0, 24.0),
-                        ));
-                    }
-                    println!("Enemy Killed!");
+                    let cause = if bullet.bounces > 0 { KillCause::Ricochet } else { KillCause::Bullet };
+                    killer.kill(enemy_entity, enemy_transform, splitter, loot, cause);
                 }
                 
             }
@@ -249,69 +351,141 @@ pub fn bullet_enemy_collision(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn enemy_player_collision(
     mut commands: Commands,
-    mut player_query: Query<(&Transform, &Collider, &mut Health, &mut PlayerStats), With<Player>>, 
+    time: Res<Time>,
+    mut player_query: Query<(Entity, &Transform, &Collider, &mut Health, &Sprite, Option<&mut StatusEffects>, Option<&Invulnerable>), (With<Player>, Without<Enemy>)>, 
     mut ev_shake: EventWriter<ScreenShakeEvent>,
-    enemy_query: Query<(Entity, &Transform, &Collider, &Damage), With<Enemy>>,
+    mut ev_damaged: EventWriter<PlayerDamaged>,
+    mut enemy_query: Query<(Entity, &Transform, &Collider, &Damage, &mut ContactAttack, &Sprite, Option<&mut StatusEffects>, Option<&Boss>), With<Enemy>>,
     sound_assets: Res<SoundAssets>,
-    game_assets: Res<GameAssets>
 ) {
-    let mut rng = rand::thread_rng();
+    let Ok((player_entity, player_transform, player_collider, mut player_health, player_sprite, mut player_status, invulnerable)) = player_query.get_single_mut() else { return };
+    let player_pos = player_transform.translation.truncate();
+    // Mỗi frame chỉ nhận tối đa một đòn, dù cả bầy cùng chạm
+    let mut is_invulnerable = invulnerable.is_some();
+
+    for (enemy_entity, enemy_transform, enemy_collider, damage, mut contact, enemy_sprite, enemy_status, boss) in enemy_query.iter_mut() {
+        contact.cooldown.tick(time.delta());
+
+        let collision = check_collision(
+            player_transform,
+            player_collider,
+            enemy_transform,
+            enemy_collider,
+        );
+
+        if collision == CollisionSide::None || is_invulnerable || !contact.cooldown.finished() {
+            continue;
+        }
+        contact.cooldown.reset();
+        is_invulnerable = true;
 
+        commands.spawn(AudioBundle {
+            source: sound_assets.hurt.clone(),
+            settings: PlaybackSettings::DESPAWN, 
+        });
+        ev_shake.send(ScreenShakeEvent { 
+            intensity: 3.0, 
+            duration: 0.1 
+        });
+        player_health.current -= damage.amount;
+        commands.entity(player_entity).insert(Invulnerable::new(PLAYER_IFRAME_TIME));
+        ev_damaged.send(PlayerDamaged { position: player_pos, amount: damage.amount });
+
+        // Đẩy hai bên ra xa nhau, boss thì đứng vững
+        let away = (player_pos - enemy_transform.translation.truncate()).normalize_or(Vec2::Y);
+        apply_status(&mut commands, player_entity, player_status.take(), player_sprite.color, &[PLAYER_KNOCKBACK], away);
+        if boss.is_none() {
+            apply_status(&mut commands, enemy_entity, enemy_status, enemy_sprite.color, &[ENEMY_KNOCKBACK], -away);
+        }
+    }
+}
 
-    if let Ok((player_transform, player_collider, mut player_health, mut player_stats)) = player_query.get_single_mut() {
-        
-        for (enemy_entity, enemy_transform, enemy_collider, damage) in enemy_query.iter() {
-            let collision = check_collision(
-                player_transform,
-                player_collider,
-                enemy_transform,
-                enemy_collider,
-            );
+/// Đếm ngược i-frame của player, nhấp nháy sprite trong lúc bất tử.
+pub fn update_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Invulnerable, &mut Visibility)>,
+) {
+    for (entity, mut invulnerable, mut visibility) in query.iter_mut() {
+        invulnerable.timer.tick(time.delta());
+
+        if invulnerable.timer.finished() {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<Invulnerable>();
+        } else if let Some(blink) = invulnerable.blink_timer.as_mut() {
+            if blink.tick(time.delta()).just_finished() {
+                *visibility = match *visibility {
+                    Visibility::Hidden => Visibility::Inherited,
+                    _ => Visibility::Hidden,
+                };
+            }
+        }
+    }
+}
 
-            if collision != CollisionSide::None {
-                commands.spawn(AudioBundle {
-                    source: sound_assets.hurt.clone(),
-                    settings: PlaybackSettings::DESPAWN, 
-                });
-                ev_shake.send(ScreenShakeEvent { 
-                    intensity: 3.0, 
-                    duration: 0.1 
-                });
-                player_health.current -= damage.amount;
-                player_stats.current_hp -= damage.amount;
-
-                if rng.gen_bool(DROP_RATE){
-                    let coin_transform = Transform::from_translation(enemy_transform.translation)
-                        .with_scale(Vec3::splat(1.0));
-
-                    commands.spawn((
-                        SpriteBundle {
-                            texture: game_assets.coin_texture.clone(),
-                            transform: coin_transform,
-                            sprite: Sprite {
-                                custom_size: Some(Vec2::splat(24.0)), 
-                                ..default()
-                            },
-                            ..default()
-                        },
-                        Coin { value: 1 }, 
-                        Collider::new(24.0, 24.0),
-                    ));
-                } 
+#[allow(clippy::type_complexity)]
+pub fn enemy_bullet_player_collision(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &Transform, &Collider, &mut Health, Option<&Invulnerable>), (With<Player>, Without<Bullet>)>,
+    mut bullet_query: Query<(Entity, &Transform, &Collider, &Bullet)>,
+    mut ev_shake: EventWriter<ScreenShakeEvent>,
+    mut ev_damaged: EventWriter<PlayerDamaged>,
+    sound_assets: Res<SoundAssets>,
+) {
+    let Ok((player_entity, player_transform, player_collider, mut player_health, invulnerable)) = player_query.get_single_mut() else { return };
+    // Cả loạt đạn chạm cùng frame cũng chỉ tính một đòn
+    let mut is_invulnerable = invulnerable.is_some();
 
-                commands.entity(enemy_entity).despawn_recursive();
+    for (bullet_entity, bullet_transform, bullet_collider, bullet) in bullet_query.iter_mut() {
+        if is_invulnerable || bullet.faction != Faction::Enemy || bullet.is_despawning {
+            continue;
+        }
 
-                if player_health.current <= 0.0 {
-                    println!("GAME OVER");
-                    player_stats.current_hp = 0.0;
-                }
-            }
+        let collision = check_collision(
+            player_transform,
+            player_collider,
+            bullet_transform,
+            bullet_collider,
+        );
+
+        if collision == CollisionSide::None {
+            continue;
         }
+        is_invulnerable = true;
+
+        commands.spawn(AudioBundle {
+            source: sound_assets.hurt.clone(),
+            settings: PlaybackSettings::DESPAWN,
+        });
+        ev_shake.send(ScreenShakeEvent {
+            intensity: 3.0,
+            duration: 0.1
+        });
+        player_health.current -= bullet.damage;
+        commands.entity(bullet_entity).despawn();
+        commands.entity(player_entity).insert(Invulnerable::new(PLAYER_IFRAME_TIME));
+        ev_damaged.send(PlayerDamaged {
+            position: player_transform.translation.truncate(),
+            amount: bullet.damage,
+        });
     }
 }
 
+/// Va chạm với quái, đạn quái rồi sóng chấn động của boss chạy nối tiếp, lệnh gắn
+/// i-frame ở giữa được áp dụng ngay để các nguồn không cùng trừ máu trong một frame.
+pub fn player_damage_systems() -> impl IntoSystemConfigs<()> {
+    (
+        enemy_player_collision,
+        apply_deferred,
+        enemy_bullet_player_collision,
+        apply_deferred,
+        update_shockwaves,
+    ).chain()
+}
+
 fn spawn_death_particles(commands: &mut Commands, position: Vec3) {
     let mut rng = thread_rng();
 
@@ -380,4 +554,86 @@ fn spawn_death_particles(commands: &mut Commands, position: Vec3) {
 //             Color::srgb(1.0, 0.5, 0.5), 
 //         );
 //     }
-// }
\ No newline at end of file
+// }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::boss::Shockwave;
+
+    fn setup() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_event::<ScreenShakeEvent>()
+            .add_event::<PlayerDamaged>()
+            .init_resource::<Time>()
+            .init_resource::<SoundAssets>()
+            .add_systems(Update, player_damage_systems());
+
+        let player = app.world_mut().spawn((
+            Player,
+            Transform::default(),
+            Collider::new(32.0, 32.0),
+            Health { current: 5.0, max: 5.0 },
+            Sprite::default(),
+        )).id();
+        (app, player)
+    }
+
+    // Vòng sóng vừa quét tới đúng chỗ player
+    fn spawn_shockwaves(world: &mut World, count: usize) {
+        for _ in 0..count {
+            world.spawn((
+                Transform::default(),
+                Sprite::default(),
+                Shockwave { radius: 0.0, max_radius: 320.0, speed: 0.0, damage: 1.0, has_hit: false },
+            ));
+        }
+    }
+
+    fn assert_single_hit(app: &mut App, player: Entity) {
+        let world = app.world_mut();
+        assert_eq!(world.get::<Health>(player).unwrap().current, 4.0);
+        assert!(world.get::<Invulnerable>(player).is_some());
+        assert_eq!(world.resource::<Events<PlayerDamaged>>().len(), 1);
+    }
+
+    // Hai viên đạn quái, một con quái và sóng chấn động cùng chạm player trong một frame chỉ trừ một đòn
+    #[test]
+    fn overlapping_hits_deal_damage_once_per_iframe_window() {
+        let (mut app, player) = setup();
+        let world = app.world_mut();
+        world.spawn((
+            Enemy,
+            Transform::from_xyz(10.0, 0.0, 0.0),
+            Collider::new(32.0, 32.0),
+            Damage { amount: 1.0 },
+            ContactAttack::new(1.0),
+            Sprite::default(),
+        ));
+        for offset in [-5.0, 5.0] {
+            world.spawn((
+                Bullet { faction: Faction::Enemy, damage: 1.0, ..default() },
+                Transform::from_xyz(0.0, offset, 0.0),
+                Collider::new(8.0, 8.0),
+            ));
+        }
+        spawn_shockwaves(world, 1);
+
+        app.update();
+
+        assert_single_hit(&mut app, player);
+        let world = app.world_mut();
+        // Đạn chưa gây sát thương thì vẫn còn nguyên
+        assert_eq!(world.query::<&Bullet>().iter(world).count(), 2);
+        assert!(world.query::<&Shockwave>().iter(world).all(|wave| !wave.has_hit));
+    }
+
+    #[test]
+    fn overlapping_shockwaves_deal_damage_once() {
+        let (mut app, player) = setup();
+        spawn_shockwaves(app.world_mut(), 2);
+
+        app.update();
+
+        assert_single_hit(&mut app, player);
+    }
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use crate::components::enemy::Health;
+use crate::components::player::{AuraEnergy, Dash, Player};
+use crate::components::stats::{PlayerStats, StatKind, StatModifier, Stats};
+use crate::resources::game_stats::GameStats;
+use crate::resources::level::LevelManager;
+use crate::resources::relics::RelicInventory;
+use crate::resources::run_rng::RunRng;
+use crate::resources::save_game::{backup_save, delete_save, read_save, write_save, SaveError, SaveSlot, SavedModifier, SavedRun, SAVE_VERSION};
+use crate::resources::shop_catalog::PurchaseHistory;
+
+/// Lưu run mỗi lần vào shop. Chạy trước `setup_shop` để RNG được lưu
+/// trước khi bốc hàng, chơi tiếp sẽ thấy đúng bàn hàng cũ.
+pub fn save_run(
+    player_query: Query<(&PlayerStats, &Stats, &Health), With<Player>>,
+    level_manager: Res<LevelManager>,
+    relics: Res<RelicInventory>,
+    history: Res<PurchaseHistory>,
+    game_stats: Res<GameStats>,
+    rng: Res<RunRng>,
+) {
+    let Ok((stats, attributes, health)) = player_query.get_single() else { return };
+
+    let run = SavedRun {
+        version: SAVE_VERSION,
+        level: level_manager.current_level,
+        difficulty: level_manager.difficulty,
+        money: stats.money,
+        spare_bullets: stats.spare_bullets,
+        bullet_effects: stats.bullet_effects.clone(),
+        health: health.current,
+        modifiers: attributes
+            .permanent_modifiers()
+            .map(|m| SavedModifier { stat: m.stat, add: m.add, multiply: m.multiply })
+            .collect(),
+        relics: relics.relics.clone(),
+        purchases: history.counts.clone(),
+        total_time: game_stats.total_time,
+        shots_fired: game_stats.shots_fired,
+        parries: game_stats.parries,
+        kills: game_stats.kills,
+        rng_state: rng.state,
+    };
+
+    match write_save(&run) {
+        Ok(()) => info!("Run saved at level {}", run.level),
+        Err(err) => warn!("Could not save run: {err}"),
+    }
+}
+
+/// Đọc lại save khi vào menu để biết có hiện nút Continue hay không.
+/// Save cũ được nâng version khi đọc. Save hỏng hoặc của bản mới hơn thì
+/// đổi sang `.bak` và báo lên menu để người chơi biết run đó không còn.
+pub fn load_save_slot(mut slot: ResMut<SaveSlot>) {
+    slot.resume = false;
+    slot.notice = None;
+    slot.run = match read_save() {
+        Ok(run) => run,
+        Err(SaveError::NoDataDir) => None,
+        Err(err) => {
+            warn!("Could not load saved run: {err}");
+            slot.notice = Some(match err {
+                SaveError::Version(_) => "Saved run is from a newer version".to_string(),
+                _ => "Saved run could not be loaded".to_string(),
+            });
+            if let Err(err) = backup_save() {
+                warn!("Could not back up saved run: {err}");
+            }
+            None
+        }
+    };
+}
+
+/// Áp dụng save lên trạng thái vừa reset khi người chơi bấm Continue.
+pub fn resume_saved_run(
+    mut slot: ResMut<SaveSlot>,
+    mut player_query: Query<(&mut PlayerStats, &mut Stats, &mut Health, &mut Dash, &mut AuraEnergy), With<Player>>,
+    mut level_manager: ResMut<LevelManager>,
+    mut relics: ResMut<RelicInventory>,
+    mut history: ResMut<PurchaseHistory>,
+    mut game_stats: ResMut<GameStats>,
+    mut rng: ResMut<RunRng>,
+) {
+    if !slot.resume {
+        return;
+    }
+    slot.resume = false;
+    let Some(run) = slot.run.take() else { return };
+    let Ok((mut stats, mut attributes, mut health, mut dash, mut aura)) = player_query.get_single_mut() else { return };
+
+    stats.money = run.money;
+    stats.spare_bullets = run.spare_bullets;
+    stats.bullet_effects = run.bullet_effects;
+    // Modifier đã lưu gồm cả vũ khí khởi đầu, bỏ cái reset vừa gắn để không cộng hai lần
+    *attributes = Stats::player();
+    for modifier in run.modifiers {
+        attributes.add_modifier(StatModifier { stat: modifier.stat, add: modifier.add, multiply: modifier.multiply, timer: None, source: None });
+    }
+    *health = Health::new(attributes.get(StatKind::MaxHp));
+    health.current = run.health.min(health.max);
+    *dash = Dash::full(&attributes);
+    *aura = AuraEnergy::full(&attributes);
+
+    level_manager.difficulty = run.difficulty;
+    level_manager.load_level(run.level);
+    relics.relics = run.relics;
+    history.counts = run.purchases;
+    game_stats.total_time = run.total_time;
+    game_stats.shots_fired = run.shots_fired;
+    game_stats.parries = run.parries;
+    game_stats.kills = run.kills;
+    rng.state = run.rng_state;
+
+    info!("Resumed saved run at level {}", run.level);
+}
+
+/// Run đã kết thúc thì không còn gì để chơi tiếp.
+pub fn clear_run_save(mut slot: ResMut<SaveSlot>) {
+    slot.run = None;
+    if let Err(err) = delete_save() {
+        warn!("Could not delete saved run: {err}");
+    }
+}
@@ -0,0 +1,219 @@
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use bevy::window::PrimaryWindow;
+use crate::resources::settings::{Settings, RESOLUTIONS};
+use crate::resources::sound::{BackgroundMusic, SoundAssets};
+
+const TITLE_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+const NORMAL_TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const HOVERED_TEXT_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
+const VOLUME_STEP: f32 = 0.1;
+const SHAKE_STEP: f32 = 0.25;
+const MAX_SHAKE: f32 = 2.0;
+
+/// Bảng cài đặt dùng chung cho menu chính và menu pause, ẩn cho tới khi mở.
+#[derive(Component)]
+pub struct SettingsPanel;
+
+#[derive(Component, Clone, Copy)]
+pub enum SettingsButton {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    DisplayMode,
+    Resolution,
+    ScreenShake,
+    VSync,
+    Back,
+}
+
+impl SettingsButton {
+    const ROWS: [SettingsButton; 8] = [
+        SettingsButton::MasterVolume,
+        SettingsButton::MusicVolume,
+        SettingsButton::SfxVolume,
+        SettingsButton::DisplayMode,
+        SettingsButton::Resolution,
+        SettingsButton::ScreenShake,
+        SettingsButton::VSync,
+        SettingsButton::Back,
+    ];
+
+    fn label(self, settings: &Settings) -> String {
+        let percent = |value: f32| (value * 100.0).round() as u32;
+        match self {
+            SettingsButton::MasterVolume => format!("MASTER: {}%", percent(settings.master_volume)),
+            SettingsButton::MusicVolume => format!("MUSIC: {}%", percent(settings.music_volume)),
+            SettingsButton::SfxVolume => format!("SFX: {}%", percent(settings.sfx_volume)),
+            SettingsButton::DisplayMode => format!("MODE: {}", settings.display_mode.label()),
+            SettingsButton::Resolution => format!("RESOLUTION: {}x{}", settings.resolution.0, settings.resolution.1),
+            SettingsButton::ScreenShake => format!("SCREEN SHAKE: {}%", percent(settings.screen_shake)),
+            SettingsButton::VSync => format!("VSYNC: {}", if settings.vsync { "ON" } else { "OFF" }),
+            SettingsButton::Back => "BACK".to_string(),
+        }
+    }
+}
+
+/// Dựng bảng cài đặt, `owner` là marker của màn hình chứa nó để được dọn cùng.
+pub fn spawn_settings_panel(commands: &mut Commands, font: &Handle<Font>, owner: impl Bundle) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(12.0),
+                display: Display::None,
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.9).into(),
+            // Chặn click xuyên xuống nút của màn hình bên dưới
+            focus_policy: FocusPolicy::Block,
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        SettingsPanel,
+        owner,
+    )).with_children(|panel| {
+        panel.spawn(TextBundle::from_section(
+            "SETTINGS",
+            TextStyle { font: font.clone(), font_size: 35.0, color: TITLE_COLOR },
+        ));
+
+        for action in SettingsButton::ROWS {
+            panel.spawn((
+                ButtonBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(6.0)),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(Color::NONE),
+                    ..default()
+                },
+                action,
+            )).with_children(|button| {
+                // Nội dung chữ do `update_settings_labels` điền
+                button.spawn(TextBundle::from_section(
+                    "",
+                    TextStyle { font: font.clone(), font_size: 22.0, color: NORMAL_TEXT_COLOR },
+                ));
+            });
+        }
+    });
+}
+
+pub fn update_settings_labels(
+    settings: Res<Settings>,
+    button_query: Query<(Ref<SettingsButton>, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (action, children) in button_query.iter() {
+        if !settings.is_changed() && !action.is_added() {
+            continue;
+        }
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.sections[0].value = action.label(&settings);
+            }
+        }
+    }
+}
+
+pub fn settings_action(
+    mut commands: Commands,
+    sound_assets: Res<SoundAssets>,
+    mut settings: ResMut<Settings>,
+    interaction_query: Query<(&Interaction, &SettingsButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut panel_query: Query<&mut Style, With<SettingsPanel>>,
+) {
+    for (interaction, action, children) in interaction_query.iter() {
+        let color = match *interaction {
+            Interaction::None => NORMAL_TEXT_COLOR,
+            _ => HOVERED_TEXT_COLOR,
+        };
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.sections[0].style.color = color;
+            }
+        }
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        commands.spawn(AudioBundle {
+            source: sound_assets.select.clone(),
+            settings: PlaybackSettings::DESPAWN,
+        });
+
+        // Mỗi lần bấm tăng một nấc, quá mức tối đa thì quay về 0
+        let step = |value: f32, step: f32, max: f32| {
+            let next = ((value + step) / step).round() * step;
+            if next > max + f32::EPSILON { 0.0 } else { next }
+        };
+        match action {
+            SettingsButton::MasterVolume => settings.master_volume = step(settings.master_volume, VOLUME_STEP, 1.0),
+            SettingsButton::MusicVolume => settings.music_volume = step(settings.music_volume, VOLUME_STEP, 1.0),
+            SettingsButton::SfxVolume => settings.sfx_volume = step(settings.sfx_volume, VOLUME_STEP, 1.0),
+            SettingsButton::DisplayMode => settings.display_mode = settings.display_mode.next(),
+            SettingsButton::Resolution => {
+                let index = RESOLUTIONS.iter().position(|r| *r == settings.resolution).unwrap_or(0);
+                settings.resolution = RESOLUTIONS[(index + 1) % RESOLUTIONS.len()];
+            }
+            SettingsButton::ScreenShake => settings.screen_shake = step(settings.screen_shake, SHAKE_STEP, MAX_SHAKE),
+            SettingsButton::VSync => settings.vsync = !settings.vsync,
+            SettingsButton::Back => {
+                for mut style in panel_query.iter_mut() {
+                    style.display = Display::None;
+                }
+                continue;
+            }
+        }
+        settings.save();
+    }
+}
+
+/// Áp dụng âm lượng tổng và nhạc nền ngay khi đổi cài đặt.
+/// GlobalVolume chỉ tính lúc âm thanh bắt đầu phát nên nhạc đang chạy phải chỉnh tay.
+pub fn apply_audio_settings(
+    settings: Res<Settings>,
+    mut global_volume: ResMut<GlobalVolume>,
+    music_query: Query<&AudioSink, With<BackgroundMusic>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    global_volume.volume = Volume::new(settings.master_volume);
+    for sink in music_query.iter() {
+        sink.set_volume(settings.master_volume * settings.music_level());
+    }
+}
+
+/// Nhân âm lượng SFX vào mọi âm thanh mới trừ nhạc nền, trước khi bevy_audio phát.
+pub fn scale_sfx_volume(
+    settings: Res<Settings>,
+    mut query: Query<&mut PlaybackSettings, (Added<PlaybackSettings>, Without<BackgroundMusic>)>,
+) {
+    for mut playback in query.iter_mut() {
+        playback.volume = Volume::new(playback.volume.get() * settings.sfx_volume);
+    }
+}
+
+pub fn apply_window_settings(
+    settings: Res<Settings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    // Cửa sổ đã được tạo đúng cài đặt lúc khởi động
+    if !settings.is_changed() || settings.is_added() {
+        return;
+    }
+    let Ok(mut window) = window_query.get_single_mut() else { return };
+
+    window.mode = settings.display_mode.window_mode();
+    window.resolution = settings.window_resolution();
+    window.present_mode = settings.present_mode();
+}
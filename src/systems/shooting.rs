@@ -1,17 +1,25 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use crate::components::player::Player;
-use crate::components::stats::PlayerStats;
-use crate::components::bullet::Bullet;
+use crate::components::stats::{PlayerStats, StatKind, Stats};
+use crate::components::bullet::{Bullet, Faction};
 use crate::components::collider::Collider;
+use crate::components::item::DroppedBullet;
+use crate::components::enemy::{Enemy, RangedAttack, Damage};
+use crate::components::status::{StatusEffect, StatusEffects};
 use crate::resources::game_stats::GameStats;
 use crate::resources::sound::SoundAssets;
 
 const BULLET_SPRITE_PATH: &str = "sprites/bullet/bullet.png";
 const BULLET_SIZE: f32 = 16.0;
-const BULLET_COST: u32 = 3;
 const BULLET_FRAMES: usize = 4;
 
+const ENEMY_BULLET_SPEED: f32 = 150.0;
+const ENEMY_BULLET_LIFETIME: f32 = 6.0;
+const ENEMY_BULLET_RANGE: f32 = 500.0;
+const ENEMY_BULLET_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+const DROPPED_BULLET_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
 #[derive(Resource)]
 pub struct BulletAssets {
     pub texture: Handle<Image>,
@@ -33,25 +41,26 @@ pub fn setup_bullet_assets(
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_bullet(
     mut commands: Commands,
     mouse_input: Res<ButtonInput<MouseButton>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
-    mut player_query: Query<(&Transform, &mut PlayerStats), With<Player>>,
+    mut player_query: Query<(Entity, &Transform, &mut PlayerStats, &Stats), With<Player>>,
     bullet_query: Query<&Bullet>,
     bullet_assets: Res<BulletAssets>,
     mut game_stats: ResMut<GameStats>,
     sound_assets: Res<SoundAssets>
 ) {
-
-    if !bullet_query.is_empty() { return; }
-
     let (camera, camera_transform) = camera_query.single();
     let window = window_query.single();
 
-    if let Ok((player_transform, mut stats)) = player_query.get_single_mut() {
-        if stats.money < BULLET_COST { return; }
+    if let Ok((player_entity, player_transform, mut stats, attributes)) = player_query.get_single_mut() {
+        let live_bullets = bullet_query.iter().filter(|bullet| bullet.owner == Some(player_entity)).count();
+        if live_bullets >= attributes.get(StatKind::MaxBullets) as usize { return; }
+
+        if stats.spare_bullets == 0 && stats.money < attributes.bullet_cost() { return; }
 
         if mouse_input.just_pressed(MouseButton::Left) {
             commands.spawn(AudioBundle{
@@ -63,8 +72,15 @@ pub fn spawn_bullet(
                 .cursor_position()
                 .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
             {
-                stats.money -= BULLET_COST;
-                let damage = stats.damage; 
+                // Dùng vỏ đạn nhặt được trước, hết mới trừ tiền
+                if stats.spare_bullets > 0 {
+                    stats.spare_bullets -= 1;
+                } else {
+                    stats.money -= attributes.bullet_cost();
+                }
+                let damage = attributes.get(StatKind::Damage);
+                let effects = stats.bullet_effects.clone();
+                let (speed, lifetime) = (attributes.get(StatKind::BulletSpeed), attributes.get(StatKind::BulletLifetime));
 
                 let player_pos = player_transform.translation.truncate();
                 let direction = (world_position - player_pos).normalize_or_zero();
@@ -80,7 +96,10 @@ pub fn spawn_bullet(
                         layout: bullet_assets.layout.clone(),
                         index: 0,
                     },
-                    Bullet::default().into_builder(direction, damage),
+                    Bullet::default()
+                        .into_builder(direction, damage, player_entity)
+                        .with_flight(speed, lifetime)
+                        .with_effects(effects),
                     Collider::new(12.0, 12.0),
                 ));
             }
@@ -89,18 +108,93 @@ pub fn spawn_bullet(
 }
 
 impl Bullet {
-    fn into_builder(mut self, direction: Vec2, damage: f32) -> Self {
+    fn into_builder(mut self, direction: Vec2, damage: f32, owner: Entity) -> Self {
         self.direction = direction;
         self.damage = damage;
+        self.owner = Some(owner);
+        // Bắn ra từ tâm player nên đã ở trong aura, không được tính là vừa bay vào
+        self.in_aura = true;
+        self
+    }
+
+    fn with_flight(mut self, speed: f32, lifetime: f32) -> Self {
+        self.speed = speed;
+        self.lifetime = Timer::from_seconds(lifetime, TimerMode::Once);
+        self
+    }
+
+    fn with_effects(mut self, effects: Vec<StatusEffect>) -> Self {
+        self.effects = effects;
         self
     }
 }
 
+#[allow(clippy::type_complexity)]
+pub fn enemy_ranged_attack(
+    mut commands: Commands,
+    time: Res<Time>,
+    bullet_assets: Res<BulletAssets>,
+    player_query: Query<&Transform, With<Player>>,
+    mut enemy_query: Query<(Entity, &Transform, &Damage, &mut RangedAttack, Option<&StatusEffects>), With<Enemy>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else { return };
+    let player_pos = player_transform.translation.truncate();
+
+    for (enemy_entity, enemy_transform, damage, mut ranged, status) in enemy_query.iter_mut() {
+        if status.is_some_and(StatusEffects::is_stunned) {
+            continue;
+        }
+
+        ranged.cooldown.tick(time.delta());
+        if !ranged.cooldown.just_finished() {
+            continue;
+        }
+
+        let enemy_pos = enemy_transform.translation.truncate();
+        if enemy_pos.distance(player_pos) > ENEMY_BULLET_RANGE {
+            continue;
+        }
+
+        let direction = (player_pos - enemy_pos).normalize_or_zero();
+
+        commands.spawn((
+            SpriteBundle {
+                texture: bullet_assets.texture.clone(),
+                transform: Transform::from_translation(enemy_pos.extend(5.0))
+                    .with_scale(Vec3::splat(2.0)),
+                sprite: Sprite {
+                    color: ENEMY_BULLET_COLOR,
+                    ..default()
+                },
+                ..default()
+            },
+            TextureAtlas {
+                layout: bullet_assets.layout.clone(),
+                index: 0,
+            },
+            Bullet {
+                direction,
+                speed: ENEMY_BULLET_SPEED,
+                lifetime: Timer::from_seconds(ENEMY_BULLET_LIFETIME, TimerMode::Once),
+                damage: damage.amount,
+                owner: Some(enemy_entity),
+                faction: Faction::Enemy,
+                ..default()
+            },
+            Collider::new(12.0, 12.0),
+        ));
+    }
+}
+
 pub fn move_and_animate_bullet(
     mut commands: Commands,
     time: Res<Time>,
+    bullet_assets: Res<BulletAssets>,
+    player_query: Query<(Entity, &PlayerStats, &Stats), With<Player>>,
     mut query: Query<(Entity, &mut Transform, &mut TextureAtlas, &mut Bullet)>,
 ) {
+    let player = player_query.get_single().ok();
+
     for (entity, mut transform, mut atlas, mut bullet) in query.iter_mut() {
         transform.translation += bullet.direction.extend(0.0) * bullet.speed * time.delta_seconds();
 
@@ -120,12 +214,41 @@ pub fn move_and_animate_bullet(
                     atlas.index += 1;
                 } else {
                     commands.entity(entity).despawn();
+
+                    // Hết tiền mua đạn thì để lại vỏ đạn, tránh kẹt không bắn được
+                    let is_broke = player.is_some_and(|(player_entity, stats, attributes)| {
+                        bullet.owner == Some(player_entity) && stats.money < attributes.bullet_cost()
+                    });
+                    if is_broke {
+                        spawn_dropped_bullet(&mut commands, &bullet_assets, transform.translation.truncate());
+                    }
                 }
             }
         }
     }
 }
 
+fn spawn_dropped_bullet(commands: &mut Commands, bullet_assets: &BulletAssets, position: Vec2) {
+    commands.spawn((
+        SpriteBundle {
+            texture: bullet_assets.texture.clone(),
+            transform: Transform::from_translation(position.extend(2.0))
+                .with_scale(Vec3::splat(2.0)),
+            sprite: Sprite {
+                color: DROPPED_BULLET_COLOR,
+                ..default()
+            },
+            ..default()
+        },
+        TextureAtlas {
+            layout: bullet_assets.layout.clone(),
+            index: 0,
+        },
+        DroppedBullet,
+        Collider::new(24.0, 24.0),
+    ));
+}
+
 const MAP_WIDTH: f32 = 1280.0;
 const MAP_HEIGHT: f32 = 720.0;
 
@@ -1,8 +1,14 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use crate::components::player::Player;
-use crate::components::stats::PlayerStats;
+use rand::Rng;
+use crate::components::player::{Player, AuraEnergy, Dash};
+use crate::components::stats::{PlayerStats, StatKind, StatModifier, Stats};
 use crate::components::enemy::Health;
 use crate::resources::level::LevelManager;
+use crate::resources::profile::Profile;
+use crate::resources::relics::RelicInventory;
+use crate::resources::run_rng::RunRng;
+use crate::resources::shop_catalog::{BuffType, PurchaseHistory, Rarity, ShopCatalog, ShopCatalogHandle};
 use crate::resources::sound::SoundAssets;
 use crate::states::AppState;
 
@@ -11,16 +17,18 @@ pub struct ShopUI;
 
 #[derive(Component)]
 pub struct ShopItem {
+    pub id: String,
     pub name: String,
     pub cost: u32,
     pub description: String,
+    pub rarity: Rarity,
     pub buff_type: BuffType,
 }
 
-#[derive(Clone, Copy)]
-pub enum BuffType {
-    Heal,
-    DamageUp,
+// Trả tiền để bốc lại toàn bộ hàng trên bàn, mỗi lần reroll đắt thêm
+#[derive(Component)]
+pub struct RerollStation {
+    pub cost: u32,
 }
 
 #[derive(Component)]
@@ -32,11 +40,37 @@ pub struct NextLevelZone;
 const FONT_PATH: &str = "fonts/pixel_3.ttf";
 const SHOP_ITEM_SCALE: f32 = 1.2; 
 const SHOP_TABLE_Y: f32 = -120.0; 
+const SHOP_ITEM_SPACING: f32 = 160.0;
+const MIN_OFFERS: usize = 3;
+const MAX_OFFERS: usize = 5;
+const REROLL_POSITION: Vec3 = Vec3::new(480.0, SHOP_TABLE_Y, 1.0);
+const REROLL_BASE_COST: u32 = 2;
+const REROLL_COST_STEP: u32 = 1;
+const INTERACT_DISTANCE: f32 = 60.0;
+const MESSAGE_TIME: f32 = 1.0;
+
+/// Danh mục shop cùng hồ sơ người chơi để lọc bỏ món chưa mở khoá.
+#[derive(SystemParam)]
+pub struct ShopOffers<'w> {
+    catalog_handle: Res<'w, ShopCatalogHandle>,
+    catalogs: Res<'w, Assets<ShopCatalog>>,
+    profile: Res<'w, Profile>,
+}
+
+impl ShopOffers<'_> {
+    fn catalog(&self) -> Option<&ShopCatalog> {
+        self.catalogs.get(&self.catalog_handle.catalog)
+    }
+}
 
 pub fn setup_shop(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut player_query: Query<&mut Transform, With<Player>>,
+    offers: ShopOffers,
+    history: Res<PurchaseHistory>,
+    mut rng: ResMut<RunRng>,
+    level_manager: Res<LevelManager>,
 ) {
     let font = asset_server.load(FONT_PATH);
     let bg_texture = asset_server.load("sprites/ui/shop_bg.png"); 
@@ -57,21 +91,34 @@ pub fn setup_shop(
         ShopUI,
     ));
 
-    spawn_shop_item(
-        &mut commands, 
-        &asset_server, 
-        Vec3::new(-220.0, SHOP_TABLE_Y, 1.0),  
-        "sprites/ui/hp.png", 
-        BuffType::Heal
-    );
-
-    spawn_shop_item(
-        &mut commands, 
-        &asset_server, 
-        Vec3::new(220.0, SHOP_TABLE_Y, 1.0),
-        "sprites/ui/increase.png", 
-        BuffType::DamageUp
-    );
+    match offers.catalog() {
+        Some(catalog) => spawn_offers(&mut commands, &asset_server, catalog, &offers.profile, &history, &mut rng, level_manager.current_level),
+        None => warn!("Shop catalog is not loaded yet, the shop will be empty"),
+    }
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(1.0, 0.84, 0.0, 0.3),
+                custom_size: Some(Vec2::new(90.0, 60.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(REROLL_POSITION),
+            ..default()
+        },
+        RerollStation { cost: REROLL_BASE_COST },
+        ShopUI,
+    )).with_children(|parent| {
+        parent.spawn(Text2dBundle {
+            text: Text::from_section("REROLL", TextStyle {
+                font: font.clone(),
+                font_size: 18.0,
+                color: Color::WHITE,
+            }),
+            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+            ..default()
+        });
+    });
 
     commands.spawn((
         SpriteBundle {
@@ -119,58 +166,103 @@ pub fn setup_shop(
     ));
 }
 
-fn spawn_shop_item(
-    commands: &mut Commands, 
-    asset_server: &Res<AssetServer>, 
-    pos: Vec3, 
-    texture_path: &str,
-    buff_type: BuffType
+/// Bốc ngẫu nhiên 3–5 món từ danh mục bằng RNG của run và bày lên bàn.
+fn spawn_offers(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    catalog: &ShopCatalog,
+    profile: &Profile,
+    history: &PurchaseHistory,
+    rng: &mut RunRng,
+    level: usize,
 ) {
-    let (name, cost, desc) = match buff_type {
-        BuffType::Heal => ("Healing Potion", 2, "+1 HP"),
-        BuffType::DamageUp => ("Power Elixir", 4, "+1 Damage"),
-    };
+    let font = asset_server.load(FONT_PATH);
+    let count = rng.gen_range(MIN_OFFERS..=MAX_OFFERS);
+    let offers = catalog.roll_offers(history, profile, rng, count);
 
-    commands.spawn((
-        SpriteBundle {
-            texture: asset_server.load(texture_path.to_string()),
-            transform: Transform::from_translation(pos).with_scale(Vec3::splat(SHOP_ITEM_SCALE)),
-            ..default()
-        },
-        ShopItem {
-            name: name.to_string(),
-            cost,
-            description: desc.to_string(),
-            buff_type,
-        },
-        ShopUI,
-    ));
+    for (i, item) in offers.iter().enumerate() {
+        let x = (i as f32 - (offers.len() - 1) as f32 / 2.0) * SHOP_ITEM_SPACING;
+        // Các loại đạn dùng chung icon, phân biệt bằng màu của hiệu ứng
+        let color = item.buff
+            .bullet_effect()
+            .and_then(|effect| effect.kind.tint())
+            .unwrap_or(Color::WHITE);
+
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.load(item.icon.clone()),
+                transform: Transform::from_xyz(x, SHOP_TABLE_Y, 1.0).with_scale(Vec3::splat(SHOP_ITEM_SCALE)),
+                sprite: Sprite {
+                    color,
+                    ..default()
+                },
+                ..default()
+            },
+            ShopItem {
+                id: item.id.clone(),
+                name: item.name.clone(),
+                cost: item.cost(level),
+                description: item.description.clone(),
+                rarity: item.rarity,
+                buff_type: item.buff,
+            },
+            ShopUI,
+        )).with_children(|parent| {
+            // Giá hiện dưới món hàng, màu theo độ hiếm
+            parent.spawn(Text2dBundle {
+                text: Text::from_section(format!("${}", item.cost(level)), TextStyle {
+                    font: font.clone(),
+                    font_size: 14.0,
+                    color: item.rarity.color(),
+                }),
+                transform: Transform::from_xyz(0.0, -30.0, 1.0),
+                ..default()
+            });
+        });
+    }
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn shop_interaction(
     mut commands: Commands,
+    time: Res<Time>,
     sound_assets: Res<SoundAssets>,
-    player_query: Query<&Transform, With<Player>>,
-    mut stats_query: Query<(&mut PlayerStats, &mut Health), With<Player>>,
+    asset_server: Res<AssetServer>,
+    mut player_query: Query<(&Transform, &mut PlayerStats, &mut Stats, &mut Health, &mut Dash, &mut AuraEnergy), With<Player>>,
     item_query: Query<(Entity, &Transform, &ShopItem)>,
+    mut reroll_query: Query<(&Transform, &mut RerollStation)>,
     mut text_query: Query<&mut Text, With<ShopInfoText>>,
     keyboard: Res<ButtonInput<KeyCode>>,
+    offers: ShopOffers,
+    mut history: ResMut<PurchaseHistory>,
+    mut relics: ResMut<RelicInventory>,
+    mut rng: ResMut<RunRng>,
+    level_manager: Res<LevelManager>,
+    // Giữ thông báo mua / reroll trên màn hình một lúc
+    mut message_timer: Local<Option<Timer>>,
 ) {
-    let player_transform = player_query.single();
-    let (mut stats, mut health) = stats_query.single_mut();
+    let (player_transform, mut stats, mut attributes, mut health, mut dash, mut aura) = player_query.single_mut();
     let mut info_text = text_query.single_mut();
 
+    if let Some(timer) = message_timer.as_mut() {
+        if !timer.tick(time.delta()).finished() {
+            return;
+        }
+        *message_timer = None;
+    }
+
     let mut near_any_item = false;
+    let pressed = keyboard.just_pressed(KeyCode::Space);
 
-    for (_, item_transform, item) in item_query.iter() {
+    for (item_entity, item_transform, item) in item_query.iter() {
         let distance = player_transform.translation.distance(item_transform.translation);
 
-        if distance < 60.0 { 
+        if distance < INTERACT_DISTANCE { 
             near_any_item = true;
             
             info_text.sections[0].value = format!(
-                "{} - Cost: ${}\nEffect: {}\n[PRESS SPACE TO BUY]", 
-                item.name, item.cost, item.description
+                "[{}] {} - Cost: ${}\nEffect: {}\n[PRESS SPACE TO BUY]", 
+                item.rarity.label(), item.name, item.cost, item.description
             );
             
             info_text.sections[0].style.color = if stats.money >= item.cost {
@@ -179,25 +271,116 @@ pub fn shop_interaction(
                  Color::srgb(1.0, 0.0, 0.0) 
             };
 
-            if keyboard.just_pressed(KeyCode::Space) {
+            if pressed {
                 if stats.money >= item.cost {
                     commands.spawn(AudioBundle{
                         source: sound_assets.item.clone(),
                         settings: PlaybackSettings::DESPAWN,
                     });
                     stats.money -= item.cost;
+                    history.record(&item.id);
+                    commands.entity(item_entity).despawn_recursive();
                     
                     match item.buff_type {
                         BuffType::Heal => {
-                            health.current += 1.0;
-                            stats.current_hp +=1.0;
+                            health.heal(1.0);
                         }
                         BuffType::DamageUp => {
-                            stats.damage += 1.0;
+                            attributes.add_modifier(StatModifier::add(StatKind::Damage, 1.0));
+                        }
+                        BuffType::EmberRounds | BuffType::FrostRounds | BuffType::HeavyRounds | BuffType::ShockRounds => {
+                            if let Some(effect) = item.buff_type.bullet_effect() {
+                                stats.add_bullet_effect(effect);
+                            }
+                        }
+                        BuffType::DashCharge => {
+                            attributes.add_modifier(StatModifier::add(StatKind::DashCharges, 1.0));
+                            dash.charges = attributes.count(StatKind::DashCharges);
+                        }
+                        BuffType::DashDistance => {
+                            attributes.add_modifier(StatModifier::add(StatKind::DashDistance, 40.0));
+                        }
+                        BuffType::AuraCapacity => {
+                            attributes.add_modifier(StatModifier::add(StatKind::AuraCapacity, 25.0));
+                            aura.current = attributes.get(StatKind::AuraCapacity);
+                        }
+                        BuffType::AuraRegen => {
+                            attributes.add_modifier(StatModifier::add(StatKind::AuraRegen, 5.0));
+                        }
+                        BuffType::AuraRadius => {
+                            attributes.add_modifier(StatModifier::add(StatKind::AuraRadius, 10.0));
+                        }
+                        BuffType::MaxHp => {
+                            attributes.add_modifier(StatModifier::add(StatKind::MaxHp, 1.0));
+                            health.set_max(attributes.get(StatKind::MaxHp));
+                            health.heal(1.0);
+                        }
+                        BuffType::MoveSpeed => {
+                            attributes.add_modifier(StatModifier::add(StatKind::MoveSpeed, 30.0));
+                        }
+                        BuffType::BulletSpeed => {
+                            attributes.add_modifier(StatModifier::add(StatKind::BulletSpeed, 50.0));
+                        }
+                        BuffType::BulletLifetime => {
+                            attributes.add_modifier(StatModifier::add(StatKind::BulletLifetime, 3.0));
+                        }
+                        BuffType::KickPower => {
+                            attributes.add_modifier(StatModifier::add(StatKind::KickSpeed, 80.0));
+                        }
+                        BuffType::MagnetRange => {
+                            attributes.add_modifier(StatModifier::add(StatKind::MagnetRange, 40.0));
+                        }
+                        BuffType::BulletDiscount => {
+                            attributes.add_modifier(StatModifier::add(StatKind::BulletCost, -1.0));
+                        }
+                        BuffType::ExtraBullet => {
+                            attributes.add_modifier(StatModifier::add(StatKind::MaxBullets, 1.0));
+                        }
+                        BuffType::Relic(relic) => {
+                            relics.add(relic);
                         }
                     }
                     
                     info_text.sections[0].value = "PURCHASE SUCCESSFUL!".to_string();
+                    *message_timer = Some(Timer::from_seconds(MESSAGE_TIME, TimerMode::Once));
+                } else {
+                    info_text.sections[0].value = "NOT ENOUGH MONEY!".to_string();
+                }
+            }
+            break;
+        }
+    }
+
+    if let Ok((station_transform, mut station)) = reroll_query.get_single_mut() {
+        if !near_any_item && player_transform.translation.distance(station_transform.translation) < INTERACT_DISTANCE {
+            near_any_item = true;
+
+            info_text.sections[0].value = format!(
+                "Reroll offers - Cost: ${}\n[PRESS SPACE TO REROLL]",
+                station.cost
+            );
+            info_text.sections[0].style.color = if stats.money >= station.cost {
+                Color::srgb(0.0, 1.0, 0.0)
+            } else {
+                Color::srgb(1.0, 0.0, 0.0)
+            };
+
+            if pressed {
+                if let (true, Some(catalog)) = (stats.money >= station.cost, offers.catalog()) {
+                    commands.spawn(AudioBundle {
+                        source: sound_assets.coin.clone(),
+                        settings: PlaybackSettings::DESPAWN,
+                    });
+                    stats.money -= station.cost;
+                    station.cost += REROLL_COST_STEP;
+
+                    for (item_entity, ..) in item_query.iter() {
+                        commands.entity(item_entity).despawn_recursive();
+                    }
+                    spawn_offers(&mut commands, &asset_server, catalog, &offers.profile, &history, &mut rng, level_manager.current_level);
+
+                    info_text.sections[0].value = "NEW OFFERS!".to_string();
+                    *message_timer = Some(Timer::from_seconds(MESSAGE_TIME, TimerMode::Once));
                 } else {
                     info_text.sections[0].value = "NOT ENOUGH MONEY!".to_string();
                 }
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+use crate::components::enemy::Health;
+use crate::components::stats::{StatKind, Stats};
+
+/// Đếm ngược modifier có thời hạn, giữ máu tối đa khớp với chỉ số MaxHp.
+pub fn update_stats(
+    time: Res<Time>,
+    mut query: Query<(&mut Stats, Option<&mut Health>)>,
+) {
+    for (mut stats, health) in query.iter_mut() {
+        stats.tick(time.delta());
+
+        let max_hp = stats.get(StatKind::MaxHp);
+        if let Some(mut health) = health.filter(|health| health.max != max_hp) {
+            health.set_max(max_hp);
+        }
+    }
+}
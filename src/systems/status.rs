@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use crate::components::collider::Collider;
+use crate::components::enemy::{EnemyLoot, EnemySpeed, Health, Splitter};
+use crate::components::status::{StatusEffect, StatusEffects};
+use crate::level::level_grid::LevelGrid;
+use crate::systems::ricochet::{EnemyKiller, KillCause};
+use crate::vfx::HitFlash;
+
+/// Gắn hiệu ứng lên entity, chưa có component thì tạo mới với màu gốc
+/// `original_color` để trả lại khi hết hiệu ứng.
+pub fn apply_status(
+    commands: &mut Commands,
+    entity: Entity,
+    status: Option<Mut<StatusEffects>>,
+    original_color: Color,
+    effects: &[StatusEffect],
+    direction: Vec2,
+) {
+    match status {
+        Some(mut status) => {
+            for effect in effects {
+                status.apply(effect, direction);
+            }
+        }
+        None => {
+            let mut status = StatusEffects::new(original_color);
+            for effect in effects {
+                status.apply(effect, direction);
+            }
+            commands.entity(entity).insert(status);
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn tick_status_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    grid: Option<Res<LevelGrid>>,
+    mut killer: EnemyKiller,
+    mut query: Query<(
+        Entity,
+        &mut StatusEffects,
+        &mut Transform,
+        &Collider,
+        &mut Sprite,
+        &mut Health,
+        Option<&mut HitFlash>,
+        Option<&mut EnemySpeed>,
+        Option<&Splitter>,
+        Option<&EnemyLoot>,
+    )>,
+) {
+    let dt = time.delta_seconds();
+
+    for (entity, mut status, mut transform, collider, mut sprite, mut health, flash, enemy_speed, splitter, loot) in query.iter_mut() {
+        status.tick(time.delta());
+
+        // Burn: sát thương liên tục theo thời gian
+        let was_alive = health.current > 0.0;
+        let burn = status.burn_per_second() * dt;
+        if burn > 0.0 {
+            health.current -= burn;
+        }
+
+        // Freeze / Stun: nhân vào tốc độ gốc, player thì nhân lúc di chuyển
+        let multiplier = status.speed_multiplier();
+        if let Some(mut speed) = enemy_speed {
+            let base = *status.base_speed.get_or_insert(speed.speed);
+            speed.speed = base * multiplier;
+        }
+
+        // Knockback: tách từng trục để trượt dọc tường thay vì xuyên qua
+        let push = status.knockback_velocity() * dt;
+        if push != Vec2::ZERO {
+            let pos = transform.translation.truncate();
+            let blocked = |p: Vec2| grid.as_ref().is_some_and(|g| g.overlaps_wall(p, collider.half_size));
+            if !blocked(pos + Vec2::new(push.x, 0.0)) {
+                transform.translation.x += push.x;
+            }
+            let pos = transform.translation.truncate();
+            if !blocked(pos + Vec2::new(0.0, push.y)) {
+                transform.translation.y += push.y;
+            }
+        }
+
+        let finished = status.is_empty();
+        let color = if finished { status.original_color } else { status.tint().unwrap_or(status.original_color) };
+        // Đang HitFlash thì để flash trả về đúng màu khi hết nháy
+        match flash {
+            Some(mut flash) => flash.original_color = color,
+            None => sprite.color = color,
+        }
+
+        if finished {
+            commands.entity(entity).remove::<StatusEffects>();
+        }
+
+        if let Some(loot) = loot {
+            if was_alive && health.current <= 0.0 {
+                // Boss miễn nhiễm hiệu ứng nên Burn không bao giờ giết boss
+                killer.kill(entity, &transform, splitter, loot, KillCause::Status);
+            }
+        }
+    }
+}
+
+/// Hết màn thì gỡ hết hiệu ứng còn lại, trả màu và tốc độ gốc.
+pub fn clear_status_effects(
+    mut commands: Commands,
+    mut query: Query<(Entity, &StatusEffects, &mut Sprite, Option<&mut EnemySpeed>)>,
+) {
+    for (entity, status, mut sprite, enemy_speed) in query.iter_mut() {
+        sprite.color = status.original_color;
+        if let (Some(base), Some(mut speed)) = (status.base_speed, enemy_speed) {
+            speed.speed = base;
+        }
+        commands.entity(entity).remove::<StatusEffects>();
+    }
+}
@@ -1,9 +1,14 @@
 use bevy::prelude::*;
-use crate::components::stats::{PlayerStats, HpText, MoneyText};
-use crate::components::player::Player; 
+use crate::components::stats::{PlayerStats, StatKind, Stats, HpText, MoneyText};
+use crate::components::player::{Player, AuraEnergy, Dash}; 
+use crate::components::power_up::{ActivePowerUps, PowerUpKind};
 use crate::states::AppState;
 use crate::resources::level::LevelManager; 
 use crate::resources::game_stats::GameStats;
+use crate::resources::relics::RelicInventory;
+use crate::components::boss::Boss;
+use crate::components::enemy::Health;
+use crate::systems::aura::KICK_COST;
 
 const FONT_SIZE: f32 = 40.0;
 const ICON_SIZE: f32 = 48.0;
@@ -21,18 +26,52 @@ pub struct TimerText;
 #[derive(Component)]
 pub struct GameUI;
 
+#[derive(Component)]
+pub struct DashText;
+
+#[derive(Component)]
+pub struct AuraBarFill;
+
+#[derive(Component)]
+pub struct RelicText;
+
+// Ô hiển thị một loại power-up, ẩn khi loại đó không hoạt động
+#[derive(Component)]
+pub struct PowerUpSlot {
+    pub kind: PowerUpKind,
+}
+
+#[derive(Component)]
+pub struct PowerUpCountdown {
+    pub kind: PowerUpKind,
+}
+
+#[derive(Component)]
+pub struct BossBar;
+
+#[derive(Component)]
+pub struct BossBarFill;
+
+const BOSS_BAR_WIDTH: f32 = 500.0;
+const BOSS_BAR_HEIGHT: f32 = 18.0;
+const AURA_BAR_WIDTH: f32 = 160.0;
+const AURA_BAR_HEIGHT: f32 = 10.0;
+const AURA_BAR_COLOR: Color = Color::srgb(0.0, 1.0, 1.0);
+const AURA_BAR_DEPLETED_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+const POWER_UP_ICON_SIZE: f32 = 16.0;
+
 //SETUP HUD
 pub fn setup_ui(
     mut commands: Commands, 
     asset_server: Res<AssetServer>,
-    player_query: Query<&PlayerStats, With<Player>>
+    player_query: Query<(&Health, &PlayerStats), With<Player>>
 ) {
     let font = asset_server.load(FONT_PATH);
     let heart_icon = asset_server.load(HEART_ICON_PATH);
     let coin_icon = asset_server.load(COIN_ICON_PATH);
 
-    let (current_hp, current_money) = if let Ok(stats) = player_query.get_single() {
-        (stats.current_hp, stats.money)
+    let (current_hp, current_money) = if let Ok((health, stats)) = player_query.get_single() {
+        (health.current, stats.money)
     } else {
         (5.0, 10)
     };
@@ -86,6 +125,73 @@ pub fn setup_ui(
                 ));
             });
 
+            // DASH
+            parent.spawn((
+                TextBundle::from_section("DASH", TextStyle { font: font.clone(), font_size: 24.0, color: Color::WHITE }),
+                DashText,
+            ));
+
+            // AURA ENERGY
+            parent.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(AURA_BAR_WIDTH),
+                    height: Val::Px(AURA_BAR_HEIGHT),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            }).with_children(|bar| {
+                bar.spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        background_color: AURA_BAR_COLOR.into(),
+                        ..default()
+                    },
+                    AuraBarFill,
+                ));
+            });
+
+            // RELICS
+            parent.spawn((
+                TextBundle::from_section("RELICS: -", TextStyle { font: font.clone(), font_size: 18.0, color: Color::srgb(1.0, 0.84, 0.0) }),
+                RelicText,
+            ));
+
+            // POWER-UPS
+            parent.spawn(NodeBundle {
+                style: Style { flex_direction: FlexDirection::Column, row_gap: Val::Px(4.0), ..default() },
+                ..default()
+            }).with_children(|column| {
+                for kind in PowerUpKind::ALL {
+                    column.spawn((
+                        NodeBundle {
+                            style: Style {
+                                display: Display::None,
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(8.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        PowerUpSlot { kind },
+                    )).with_children(|slot| {
+                        slot.spawn(NodeBundle {
+                            style: Style { width: Val::Px(POWER_UP_ICON_SIZE), height: Val::Px(POWER_UP_ICON_SIZE), ..default() },
+                            background_color: kind.color().into(),
+                            ..default()
+                        });
+                        slot.spawn((
+                            TextBundle::from_section(kind.label(), TextStyle { font: font.clone(), font_size: 18.0, color: kind.color() }),
+                            PowerUpCountdown { kind },
+                        ));
+                    });
+                }
+            });
+
             // WAVE INFO
             parent.spawn((
                 TextBundle::from_section("Wave 1", TextStyle { font: font.clone(), font_size: 30.0, color: Color::srgb(0.0, 1.0, 1.0), }),
@@ -108,16 +214,86 @@ pub fn setup_ui(
         TimerText,
         GameUI, 
     ));
+
+    // BOSS HP BAR (ẩn cho tới khi boss xuất hiện)
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(20.0),
+                    left: Val::Percent(50.0),
+                    margin: UiRect::left(Val::Px(-BOSS_BAR_WIDTH / 2.0)),
+                    width: Val::Px(BOSS_BAR_WIDTH),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(6.0),
+                    display: Display::None,
+                    ..default()
+                },
+                ..default()
+            },
+            BossBar,
+            GameUI,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "GOLEM KING",
+                TextStyle { font: font.clone(), font_size: 24.0, color: Color::srgb(1.0, 0.4, 0.4) },
+            ));
+            parent.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(BOSS_BAR_WIDTH),
+                    height: Val::Px(BOSS_BAR_HEIGHT),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            }).with_children(|bar| {
+                bar.spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        background_color: Color::srgb(0.8, 0.1, 0.1).into(),
+                        ..default()
+                    },
+                    BossBarFill,
+                ));
+            });
+        });
 }
 
+pub fn update_boss_bar(
+    boss_query: Query<&Health, With<Boss>>,
+    mut bar_query: Query<&mut Style, (With<BossBar>, Without<BossBarFill>)>,
+    mut fill_query: Query<&mut Style, (With<BossBarFill>, Without<BossBar>)>,
+) {
+    let boss_health = boss_query.get_single().ok();
+
+    for mut style in bar_query.iter_mut() {
+        style.display = if boss_health.is_some() { Display::Flex } else { Display::None };
+    }
+
+    if let Some(health) = boss_health {
+        let ratio = (health.current / health.max).clamp(0.0, 1.0);
+        for mut style in fill_query.iter_mut() {
+            style.width = Val::Percent(ratio * 100.0);
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
 pub fn update_ui(
-    player_query: Query<&PlayerStats, (With<Player>, Changed<PlayerStats>)>,
+    player_query: Query<(&Health, &PlayerStats), (With<Player>, Or<(Changed<Health>, Changed<PlayerStats>)>)>,
     mut hp_text_query: Query<&mut Text, (With<HpText>, Without<MoneyText>)>,
     mut money_text_query: Query<&mut Text, (With<MoneyText>, Without<HpText>)>,
 ) {
-    if let Ok(stats) = player_query.get_single() {
+    if let Ok((health, stats)) = player_query.get_single() {
         for mut text in hp_text_query.iter_mut() {
-            text.sections[0].value = format!("{:.0}", stats.current_hp);
+            text.sections[0].value = format!("{:.0}", health.current.max(0.0));
         }
         for mut text in money_text_query.iter_mut() {
             text.sections[0].value = format!("{}", stats.money);
@@ -125,6 +301,67 @@ pub fn update_ui(
     }
 }
 
+pub fn update_dash_ui(
+    player_query: Query<(&Dash, &Stats), With<Player>>,
+    mut query: Query<&mut Text, With<DashText>>,
+) {
+    let Ok((dash, stats)) = player_query.get_single() else { return };
+    let max_charges = stats.count(StatKind::DashCharges);
+
+    for mut text in query.iter_mut() {
+        text.sections[0].value = if dash.charges < max_charges {
+            format!("DASH {}/{} ({:.1}s)", dash.charges, max_charges, dash.recharge.remaining_secs())
+        } else {
+            format!("DASH {}/{}", dash.charges, max_charges)
+        };
+        text.sections[0].style.color = if dash.charges > 0 {
+            Color::WHITE
+        } else {
+            Color::srgb(0.5, 0.5, 0.5)
+        };
+    }
+}
+
+pub fn update_aura_bar(
+    player_query: Query<(&AuraEnergy, &Stats), With<Player>>,
+    mut fill_query: Query<(&mut Style, &mut BackgroundColor), With<AuraBarFill>>,
+) {
+    let Ok((energy, stats)) = player_query.get_single() else { return };
+    let ratio = (energy.current / stats.get(StatKind::AuraCapacity)).clamp(0.0, 1.0);
+    let color = if energy.can_spend(KICK_COST) { AURA_BAR_COLOR } else { AURA_BAR_DEPLETED_COLOR };
+
+    for (mut style, mut background) in fill_query.iter_mut() {
+        style.width = Val::Percent(ratio * 100.0);
+        *background = color.into();
+    }
+}
+
+pub fn update_relic_ui(
+    relics: Res<RelicInventory>,
+    mut query: Query<&mut Text, With<RelicText>>,
+) {
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("RELICS: {}", relics.names());
+    }
+}
+
+pub fn update_power_up_hud(
+    player_query: Query<&ActivePowerUps, With<Player>>,
+    mut slot_query: Query<(&PowerUpSlot, &mut Style)>,
+    mut text_query: Query<(&PowerUpCountdown, &mut Text)>,
+) {
+    let Ok(power_ups) = player_query.get_single() else { return };
+
+    for (slot, mut style) in slot_query.iter_mut() {
+        style.display = if power_ups.is_active(slot.kind) { Display::Flex } else { Display::None };
+    }
+    for (countdown, mut text) in text_query.iter_mut() {
+        if let Some(power_up) = power_ups.get(countdown.kind) {
+            text.sections[0].value = format!("{} {:.1}s", countdown.kind.label(), power_up.timer.remaining_secs());
+        }
+    }
+}
+
 pub fn update_wave_ui(
     level_manager: Res<LevelManager>,
     mut query: Query<&mut Text, With<WaveText>>,
@@ -134,6 +371,11 @@ pub fn update_wave_ui(
             if level_manager.level_completed {
                 text.sections[0].value = "VICTORY!".to_string();
                 text.sections[0].style.color = Color::srgb(0.0, 1.0, 0.0);
+            } else if level_manager.current_waves_data
+                .get(level_manager.current_wave_index)
+                .is_some_and(|wave| wave.is_boss())
+            {
+                text.sections[0].value = "BOSS WAVE".to_string();
             } else {
                 let current = level_manager.current_wave_index + 1;
                 text.sections[0].value = format!("Wave {}", current);
@@ -155,11 +397,11 @@ pub fn update_timer_ui(
 }
 
 pub fn check_game_over(
-    player_query: Query<&PlayerStats, With<Player>>,
+    player_query: Query<&Health, With<Player>>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
-    if let Ok(stats) = player_query.get_single() {
-        if stats.current_hp <= 0.0 {
+    if let Ok(health) = player_query.get_single() {
+        if health.current <= 0.0 {
             info!("HP is 0! Game Over.");
             next_state.set(AppState::GameOver);
         }
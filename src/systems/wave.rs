@@ -1,31 +1,37 @@
 use bevy::prelude::*;
 use rand::Rng;
-use crate::components::enemy::{Enemy, Health, Damage, EnemyAnimationTimer, EnemySpeed};
+use bevy::ecs::system::SystemParam;
+use std::sync::Arc;
+use crate::components::enemy::{Enemy, Health, Damage, EnemyAnimationTimer, EnemySpeed, EnemyLoot, CrowdAgent, ContactAttack, RangedAttack, Shield, ShieldVisual, Splitter};
 use crate::components::collider::Collider;
+use crate::components::boss::{Boss, WeakPoint, WeakPointVisual};
 use crate::resources::level::LevelManager;
 use crate::resources::game_config::EnemyType; 
+use crate::resources::enemy_archetypes::{Behaviour, EnemyArchetype, EnemyArchetypes, EnemyAssets, EnemyRoster, EnemySprite};
 use crate::states::AppState;
 
 
 const TILE_SIZE: f32 = 48.0;
 const MAP_COLS: f32 = 28.0;
 const MAP_ROWS: f32 = 15.0;
-const GOLEM_SIZE: f32 = 64.0;
-const GOLEM_HITBOX: f32 = 25.0;
 const MAX_LEVEL: usize = 3;
+const SHIELD_SIZE: Vec2 = Vec2::new(5.0, 30.0);
+const SHIELD_COLOR: Color = Color::srgb(0.6, 0.9, 1.0);
+const WEAK_POINT_SIZE: Vec2 = Vec2::new(6.0, 6.0);
+const WEAK_POINT_COLOR: Color = Color::srgb(1.0, 0.9, 0.2);
+const CONTACT_COOLDOWN: f32 = 1.0;
 
 
 pub fn wave_system(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    spawner: EnemySpawner,
     time: Res<Time>,
     mut level_manager: ResMut<LevelManager>,
     mut next_state: ResMut<NextState<AppState>>, 
     live_enemies: Query<Entity, With<Enemy>>,
     mut level_finish_timer: Local<Option<Timer>>,
 ) {
-    if level_manager.level_completed {
+    if level_manager.level_completed || !spawner.is_ready() {
         return;
     }
 
@@ -35,6 +41,10 @@ pub fn wave_system(
     let all_spawned = level_manager.enemies_spawned >= current_wave_data.enemy_count;
     let no_enemies_alive = live_enemies.iter().count() == 0;
 
+    // Quái con sinh ra khi Splitter chết được spawn trong cùng command buffer
+    // với lệnh despawn quái mẹ, nên query luôn thấy ít nhất một trong hai:
+    // wave không kết thúc sớm, và quái con không tính vào enemies_spawned
+    // nên cũng không làm wave bị treo.
     if all_spawned && no_enemies_alive {
         if level_manager.current_wave_index < level_manager.current_waves_data.len() - 1 {
             level_manager.current_wave_index += 1;
@@ -74,12 +84,11 @@ pub fn wave_system(
             level_manager.spawn_timer.set_duration(std::time::Duration::from_secs_f32(interval));
             level_manager.spawn_timer.reset();
 
-            spawn_single_enemy(
+            spawner.spawn(
                 &mut commands, 
-                &asset_server, 
-                &mut texture_atlas_layouts, 
-                &level_manager, 
-                current_wave_data.enemy_type 
+                &current_wave_data.enemy_type,
+                level_manager.difficulty_multiplier, 
+                random_spawn_position(),
             );
             
             level_manager.enemies_spawned += 1;
@@ -87,27 +96,9 @@ pub fn wave_system(
     }
 }
 
-// HÀM SPAWN CHI TIẾT
-fn spawn_single_enemy(
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
-    level_manager: &LevelManager,
-    enemy_type: EnemyType,
-) {
+fn random_spawn_position() -> Vec2 {
     let mut rng = rand::thread_rng();
 
-    // 1. Tính Stats dựa trên Multiplier và Enemy Type
-    let multiplier = level_manager.difficulty_multiplier;
-    
-    let (base_hp, base_speed, color_tint, scale) = match enemy_type {
-        EnemyType::Normal => (2.0, 100.0, Color::WHITE,1.5),
-        EnemyType::Tank => (3.0, 60.0, Color::srgb(0.5, 0.5, 1.0),3.0),   
-        EnemyType::Speed => (1.0, 180.0, Color::srgb(1.0, 0.5, 0.5),1.0), 
-    };
-
-    let final_hp = base_hp * multiplier; 
-
     let range_x = (MAP_COLS * TILE_SIZE) / 2.0 - (TILE_SIZE * 2.5);
     let range_y = (MAP_ROWS * TILE_SIZE) / 2.0 - (TILE_SIZE * 2.5);
     let corners = [
@@ -118,27 +109,223 @@ fn spawn_single_enemy(
     let jitter_x = rng.gen_range(-20.0..20.0);
     let jitter_y = rng.gen_range(-20.0..20.0);
 
-    let texture = asset_server.load("sprites/enemy/Golem_Armor_Run.png");
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(GOLEM_SIZE as u32), 4, 1, None, None);
-    let texture_atlas_layout = layouts.add(layout);
+    spawn_pos + Vec2::new(jitter_x, jitter_y)
+}
+
+/// Gom các resource cần để spawn quái theo archetype, dùng chung cho wave,
+/// quái tách đôi và boss gọi đệ tử.
+#[derive(SystemParam)]
+pub struct EnemySpawner<'w> {
+    enemy_assets: Res<'w, EnemyAssets>,
+    archetypes: Res<'w, EnemyArchetypes>,
+    rosters: Res<'w, Assets<EnemyRoster>>,
+}
+
+impl EnemySpawner<'_> {
+    pub fn is_ready(&self) -> bool {
+        self.archetypes.is_loaded(&self.rosters) && !self.enemy_assets.sprites.is_empty()
+    }
+
+    pub fn spawn(&self, commands: &mut Commands, enemy_type: &EnemyType, multiplier: f32, position: Vec2) {
+        let (Some(archetype), Some(sprite)) = (
+            self.archetypes.get(&self.rosters, enemy_type),
+            self.enemy_assets.sprites.get(&enemy_type.0),
+        ) else {
+            warn!("Unknown enemy archetype '{}'", enemy_type.0);
+            return;
+        };
 
-    commands.spawn((
+        spawn_single_enemy(commands, archetype, sprite, multiplier, position);
+    }
+}
+
+// HÀM SPAWN CHI TIẾT
+fn spawn_single_enemy(
+    commands: &mut Commands,
+    archetype: &EnemyArchetype,
+    sprite: &EnemySprite,
+    multiplier: f32,
+    position: Vec2,
+) {
+    let mut rng = rand::thread_rng();
+
+    // 1. Tính Stats dựa trên Multiplier và archetype
+    let final_hp = archetype.hp * multiplier; 
+    let color_tint = archetype.color();
+
+    let mut enemy = commands.spawn((
         SpriteBundle {
-            texture,
-            transform: Transform::from_xyz(spawn_pos.x + jitter_x, spawn_pos.y + jitter_y, 1.0)
-                .with_scale(Vec3::splat(scale)),
+            texture: sprite.texture.clone(),
+            transform: Transform::from_xyz(position.x, position.y, 1.0)
+                .with_scale(Vec3::splat(archetype.scale)),
             sprite: Sprite {
                 color: color_tint, 
                 ..default()
             },
             ..default()
         },
-        TextureAtlas { layout: texture_atlas_layout, index: 0 },
-        EnemyAnimationTimer::default(),
+        TextureAtlas { layout: sprite.layout.clone(), index: 0 },
+        EnemyAnimationTimer::new(archetype.animation_fps, archetype.frame_count),
         Enemy,
         Health::new(final_hp),
-        Damage { amount: 1.0 },
-        Collider::new(GOLEM_HITBOX*scale, GOLEM_HITBOX*scale),
-        EnemySpeed { speed: base_speed },
+        Damage { amount: archetype.damage },
+        Collider::new(archetype.collider.0, archetype.collider.1),
+        EnemySpeed { speed: archetype.speed },
+        EnemyLoot { table: Arc::clone(&archetype.loot) },
+        ContactAttack::new(CONTACT_COOLDOWN),
+        CrowdAgent { flank_side: if rng.gen_bool(0.5) { 1.0 } else { -1.0 } },
     ));
-}
\ No newline at end of file
+
+    match &archetype.behaviour {
+        Behaviour::Chase => {}
+        Behaviour::Boss { summon } => {
+            enemy.insert((Boss::new(color_tint, EnemyType::new(summon)), WeakPoint { facing: Vec2::NEG_X })).with_children(|parent| {
+                parent.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: WEAK_POINT_COLOR,
+                            custom_size: Some(WEAK_POINT_SIZE),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                        ..default()
+                    },
+                    WeakPointVisual,
+                ));
+            });
+        }
+        Behaviour::Splitter { child, min_children, max_children } => {
+            enemy.insert(Splitter {
+                child: EnemyType::new(child),
+                children: rng.gen_range(*min_children..=*max_children),
+                difficulty_multiplier: multiplier,
+            });
+        }
+        Behaviour::Shielded => {
+            enemy.insert(Shield { facing: Vec2::X }).with_children(|parent| {
+                parent.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: SHIELD_COLOR,
+                            custom_size: Some(SHIELD_SIZE),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                        ..default()
+                    },
+                    ShieldVisual,
+                ));
+            });
+        }
+        Behaviour::Ranged { fire_interval, preferred_distance } => {
+            let mut cooldown = Timer::from_seconds(*fire_interval, TimerMode::Repeating);
+            // Lệch pha để cả bầy không bắn cùng một lúc
+            cooldown.set_elapsed(std::time::Duration::from_secs_f32(rng.gen_range(0.0..*fire_interval)));
+            enemy.insert(RangedAttack {
+                cooldown,
+                preferred_distance: *preferred_distance,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::collections::HashSet;
+    use std::time::Duration;
+    use crate::components::bullet::Bullet;
+    use crate::components::player::Player;
+    use crate::systems::shooting::{enemy_ranged_attack, setup_bullet_assets, BulletAssets};
+
+    const SPAWNS: usize = 1_000;
+
+    fn layout_count(app: &App) -> usize {
+        app.world().resource::<Assets<TextureAtlasLayout>>().len()
+    }
+
+    // Handle (texture, layout) của mọi entity khớp điều kiện lọc, bỏ trùng
+    fn handle_ids<F: bevy::ecs::query::QueryFilter>(world: &mut World) -> HashSet<(AssetId<Image>, AssetId<TextureAtlasLayout>)> {
+        world
+            .query_filtered::<(&Handle<Image>, &TextureAtlas), F>()
+            .iter(world)
+            .map(|(texture, atlas)| (texture.id(), atlas.layout.id()))
+            .collect()
+    }
+
+    // Spawn rồi despawn 1.000 quái và đạn: mọi entity phải dùng đúng handle đã cache,
+    // số layout không được tăng
+    #[test]
+    fn asset_count_stays_flat_over_many_spawns() {
+        let mut app = App::new();
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_asset::<EnemyRoster>()
+            .init_resource::<Time>();
+
+        let roster = EnemyRoster::builtin();
+        let mut ids: Vec<String> = roster.archetypes.keys().cloned().collect();
+        ids.sort();
+
+        let world = app.world_mut();
+        let enemy_assets = world.resource_scope(|world, asset_server: Mut<AssetServer>| {
+            let mut layouts = world.resource_mut::<Assets<TextureAtlasLayout>>();
+            EnemyAssets::from_roster(&roster, &asset_server, &mut layouts)
+        });
+        let handle = world.resource_mut::<Assets<EnemyRoster>>().add(roster);
+        world.insert_resource(EnemyArchetypes { roster: handle });
+        world.insert_resource(enemy_assets);
+        world.spawn((Player, Transform::default()));
+        world.run_system_once(setup_bullet_assets);
+        app.update();
+
+        let before = layout_count(&app);
+
+        for i in 0..SPAWNS {
+            let enemy_type = EnemyType(ids[i % ids.len()].clone());
+            app.world_mut().run_system_once(move |mut commands: Commands, spawner: EnemySpawner| {
+                assert!(spawner.is_ready());
+                spawner.spawn(&mut commands, &enemy_type, 1.0, Vec2::new(100.0, 0.0));
+            });
+        }
+
+        // Cho mọi quái bắn xa đúng một phát
+        let world = app.world_mut();
+        for mut ranged in world.query::<&mut RangedAttack>().iter_mut(world) {
+            let duration = ranged.cooldown.duration();
+            ranged.cooldown.set_elapsed(duration - Duration::from_millis(1));
+        }
+        world.resource_mut::<Time>().advance_by(Duration::from_millis(10));
+        world.run_system_once(enemy_ranged_attack);
+
+        let world = app.world_mut();
+        let enemies = world.query_filtered::<Entity, With<Enemy>>().iter(world).count();
+        let bullets: Vec<Entity> = world.query_filtered::<Entity, With<Bullet>>().iter(world).collect();
+        assert!(enemies >= SPAWNS);
+        assert!(!bullets.is_empty());
+
+        let cached_enemy: HashSet<_> = world
+            .resource::<EnemyAssets>()
+            .sprites
+            .values()
+            .map(|sprite| (sprite.texture.id(), sprite.layout.id()))
+            .collect();
+        let enemy_handles = handle_ids::<With<Enemy>>(world);
+        assert!(enemy_handles.is_subset(&cached_enemy), "enemy spawned with a handle that is not in EnemyAssets");
+        assert!(enemy_handles.len() <= ids.len());
+
+        let bullet_assets = world.resource::<BulletAssets>();
+        let cached_bullet = (bullet_assets.texture.id(), bullet_assets.layout.id());
+        assert_eq!(handle_ids::<With<Bullet>>(world), HashSet::from([cached_bullet]));
+
+        let spawned: Vec<Entity> = world.query_filtered::<Entity, With<Enemy>>().iter(world).chain(bullets).collect();
+        for entity in spawned {
+            world.entity_mut(entity).despawn_recursive();
+        }
+        app.update();
+
+        assert_eq!(layout_count(&app), before);
+    }
+}
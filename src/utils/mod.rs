@@ -1 +1,3 @@
-pub mod math;
\ No newline at end of file
+pub mod math;
+pub mod ron_loader;
+pub mod storage;
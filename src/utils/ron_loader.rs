@@ -0,0 +1,82 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{Asset, AssetLoader, AsyncReadExt, LoadContext};
+use serde::Deserialize;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Loader dùng chung cho các file dữ liệu game viết bằng RON
+/// (archetype quái, ...). Mỗi loại asset đăng ký một đuôi file riêng.
+pub struct RonAssetLoader<A> {
+    extensions: &'static [&'static str],
+    _marker: PhantomData<fn() -> A>,
+}
+
+impl<A> RonAssetLoader<A> {
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Kiểm tra dữ liệu ngay sau khi parse. Giá trị làm game panic thì sửa
+/// về giá trị an toàn và báo `error!` để designer biết mà chỉnh file.
+pub trait Validate {
+    fn validate(&mut self) {}
+}
+
+#[derive(Debug)]
+pub enum RonLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for RonLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RonLoaderError::Io(err) => write!(f, "could not read data file: {err}"),
+            RonLoaderError::Ron(err) => write!(f, "invalid RON data: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for RonLoaderError {}
+
+impl From<std::io::Error> for RonLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        RonLoaderError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for RonLoaderError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        RonLoaderError::Ron(err)
+    }
+}
+
+impl<A> AssetLoader for RonAssetLoader<A>
+where
+    A: Asset + Validate + for<'de> Deserialize<'de>,
+{
+    type Asset = A;
+    type Settings = ();
+    type Error = RonLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut asset: A = ron::de::from_bytes(&bytes)?;
+        asset.validate();
+        Ok(asset)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
@@ -0,0 +1,37 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const APP_DIR_NAME: &str = "last_ricochet";
+
+/// Thư mục dữ liệu của game trong thư mục người dùng, theo quy ước từng OS:
+/// `%APPDATA%` trên Windows, `~/Library/Application Support` trên macOS,
+/// `$XDG_DATA_HOME` hoặc `~/.local/share` trên Linux.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+    base.map(|dir| dir.join(APP_DIR_NAME))
+}
+
+pub fn data_file(name: &str) -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(name))
+}
+
+// Ghi ra file tạm rồi đổi tên để tắt game giữa chừng không để lại file hỏng
+pub fn write_atomic(path: &PathBuf, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
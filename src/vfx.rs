@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use rand::Rng;
+use crate::resources::settings::Settings;
 
 #[derive(Event)]
 pub struct ScreenShakeEvent {
@@ -7,6 +8,29 @@ pub struct ScreenShakeEvent {
     pub duration: f32,
 }
 
+// Khựng cả game trong chốc lát (tính theo thời gian thật)
+#[derive(Event)]
+pub struct HitStopEvent {
+    pub duration: f32,
+}
+
+#[derive(Event)]
+pub struct ScreenFlashEvent {
+    pub color: Color,
+    pub duration: f32,
+}
+
+#[derive(Resource, Default)]
+struct HitStop {
+    timer: Option<Timer>,
+}
+
+#[derive(Component)]
+struct ScreenFlash {
+    timer: Timer,
+    alpha: f32,
+}
+
 #[derive(Component)]
 pub struct CameraShaker {
     pub intensity: f32,
@@ -25,12 +49,18 @@ fn handle_screen_shake(
     mut events: EventReader<ScreenShakeEvent>,
     mut camera_query: Query<(Entity, &mut Transform, Option<&mut CameraShaker>), With<Camera2d>>,
     time: Res<Time>,
+    settings: Res<Settings>,
 ) {
     let mut rng = rand::thread_rng();
     for (camera_entity, mut transform, shaker_opt) in camera_query.iter_mut() {
         for event in events.read() {
+            // Độ rung nhân theo cài đặt, tắt hẳn thì bỏ qua (gen_range không nhận khoảng rỗng)
+            let intensity = event.intensity * settings.screen_shake;
+            if intensity <= 0.0 {
+                continue;
+            }
             commands.entity(camera_entity).insert(CameraShaker {
-                intensity: event.intensity,
+                intensity,
                 timer: Timer::from_seconds(event.duration, TimerMode::Once),
                 original_pos: None,
             });
@@ -73,14 +103,75 @@ pub fn update_hit_flash(
     }
 }
 
+fn handle_hit_stop(
+    mut events: EventReader<HitStopEvent>,
+    mut hit_stop: ResMut<HitStop>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+) {
+    for event in events.read() {
+        hit_stop.timer = Some(Timer::from_seconds(event.duration, TimerMode::Once));
+        virtual_time.set_relative_speed(0.0);
+    }
+
+    if let Some(timer) = hit_stop.timer.as_mut() {
+        if timer.tick(real_time.delta()).finished() {
+            virtual_time.set_relative_speed(1.0);
+            hit_stop.timer = None;
+        }
+    }
+}
+
+fn handle_screen_flash(
+    mut commands: Commands,
+    mut events: EventReader<ScreenFlashEvent>,
+    mut flash_query: Query<(Entity, &mut ScreenFlash, &mut BackgroundColor)>,
+    real_time: Res<Time<Real>>,
+) {
+    for event in events.read() {
+        commands.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: event.color.into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            ScreenFlash {
+                timer: Timer::from_seconds(event.duration, TimerMode::Once),
+                alpha: event.color.alpha(),
+            },
+        ));
+    }
+
+    // Mờ dần theo thời gian thật để vẫn chạy trong lúc hit-stop
+    for (entity, mut flash, mut background) in flash_query.iter_mut() {
+        flash.timer.tick(real_time.delta());
+        if flash.timer.finished() {
+            commands.entity(entity).despawn();
+        } else {
+            background.0.set_alpha(flash.alpha * flash.timer.fraction_remaining());
+        }
+    }
+}
+
 pub struct VfxPlugin;
 
 impl Plugin for VfxPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<ScreenShakeEvent>()
+           .add_event::<HitStopEvent>()
+           .add_event::<ScreenFlashEvent>()
+           .init_resource::<HitStop>()
            .add_systems(Update, (
                handle_screen_shake,
-               update_hit_flash
+               update_hit_flash,
+               handle_hit_stop,
+               handle_screen_flash,
            ));
     }
 }
\ No newline at end of file